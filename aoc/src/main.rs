@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use aoc_common::config::Config;
+use clap::{Parser, Subcommand};
+
+mod report;
+mod run;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run all days and write a self-contained HTML report
+    Report {
+        /// HTML file to write (default: report.html in the outputs directory)
+        #[arg(short, long)]
+        out: Option<String>,
+
+        /// directory containing the day-* crates
+        #[arg(short, long, default_value = ".")]
+        root: String,
+
+        /// maximum run time of a day, in seconds
+        #[arg(short, long, default_value_t = 60)]
+        timeout: u64,
+    },
+}
+
+fn report(out: Option<String>, root: &str, timeout: Duration) -> std::io::Result<()> {
+    let config = Config::find(Path::new(root))?;
+    let inputs = config.dir("inputs", ".");
+    let render_dir = config.dir("outputs", "output");
+    std::fs::create_dir_all(&render_dir)?;
+    let out = out.map_or(render_dir.join("report.html"), PathBuf::from);
+
+    let mut results = Vec::new();
+    for day in run::discover(Path::new(root))? {
+        println!("running {}", day.name);
+        let input = inputs.join(&day.name).join("input.txt");
+        let render = run::GRID_DAYS
+            .iter()
+            .any(|(name, _)| *name == day.name)
+            .then(|| render_dir.join(format!("{}.svg", day.name)));
+        let outcome = run::run(&day, &input, render.as_deref(), timeout)?;
+        results.push((day, outcome));
+    }
+
+    report::write(&out, &results)?;
+    println!("report written to {}", out.display());
+    Ok(())
+}
+
+fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Report { out, root, timeout } => report(out, &root, Duration::from_secs(timeout)),
+    }
+}
@@ -0,0 +1,110 @@
+// Self-contained HTML report of a full run.
+//
+// Everything is inlined (style and SVG renderings) so the page can be
+// opened offline and shared as a single file.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::run::{Day, Outcome, Status, GRID_DAYS};
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; background: #fafafa; color: #222; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 4px 10px; text-align: left; vertical-align: top; }
+th { background: #eee; }
+pre { margin: 0; }
+.valid { color: #2a7d2a; }
+.invalid, .failed { color: #b02020; }
+.unchecked { color: #777; }
+.rendering svg { max-width: 100%; height: auto; border: 1px solid #ccc; }
+";
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// CSS class and short description of a status
+fn describe(status: &Status) -> (&'static str, String) {
+    match status {
+        Status::BuildFailed(_) => ("failed", "build failed".to_string()),
+        Status::RunFailed(_) => ("failed", "run failed".to_string()),
+        Status::TimedOut => ("failed", "timed out".to_string()),
+        Status::Invalid(missing) => ("invalid", format!("missing {}", missing.join(", "))),
+        Status::Valid => ("valid", "valid".to_string()),
+        Status::Unchecked => ("unchecked", "no expected answers".to_string()),
+    }
+}
+
+fn write_summary<W: Write>(out: &mut W, results: &[(Day, Outcome)]) -> std::io::Result<()> {
+    writeln!(out, "<table>")?;
+    writeln!(
+        out,
+        "<tr><th>Day</th><th>Answers</th><th>Time</th><th>Status</th></tr>"
+    )?;
+    for (day, outcome) in results {
+        let (class, status) = describe(&outcome.status);
+        writeln!(
+            out,
+            "<tr><td><a href=\"#{0}\">{0}</a></td><td><pre>{1}</pre></td><td>{2:.3} s</td><td class=\"{3}\">{4}</td></tr>",
+            escape(&day.name),
+            escape(&outcome.answers.join("\n")),
+            outcome.elapsed.as_secs_f64(),
+            class,
+            escape(&status)
+        )?;
+    }
+    writeln!(out, "</table>")
+}
+
+fn write_details<W: Write>(out: &mut W, day: &Day, outcome: &Outcome) -> std::io::Result<()> {
+    writeln!(out, "<h2 id=\"{0}\">{0}</h2>", escape(&day.name))?;
+
+    if let Some(svg) = &outcome.rendering {
+        let what = GRID_DAYS
+            .iter()
+            .find(|(name, _)| *name == day.name)
+            .map_or("grid", |(_, what)| what);
+        writeln!(out, "<h3>{}</h3>", what)?;
+        writeln!(out, "<div class=\"rendering\">{}</div>", svg)?;
+    }
+
+    if let Status::BuildFailed(log) | Status::RunFailed(log) = &outcome.status {
+        writeln!(
+            out,
+            "<h3>Errors</h3><pre class=\"failed\">{}</pre>",
+            escape(log)
+        )?;
+    }
+
+    writeln!(
+        out,
+        "<details><summary>Output ({} lines)</summary><pre>{}</pre></details>",
+        outcome.output.lines().count(),
+        escape(&outcome.output)
+    )
+}
+
+/// Write the report of all the `results` to `filename`
+pub fn write(filename: &Path, results: &[(Day, Outcome)]) -> std::io::Result<()> {
+    let mut out = BufWriter::new(File::create(filename)?);
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html><head><meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>Advent of Code 2023</title>")?;
+    writeln!(out, "<style>{}</style>", STYLE)?;
+    writeln!(out, "</head><body>")?;
+    writeln!(out, "<h1>Advent of Code 2023</h1>")?;
+
+    write_summary(&mut out, results)?;
+    for (day, outcome) in results {
+        write_details(&mut out, day, outcome)?;
+    }
+
+    writeln!(out, "</body></html>")?;
+    out.flush()
+}
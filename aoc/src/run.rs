@@ -0,0 +1,293 @@
+// Build and run the solver of every day.
+//
+// Each day is a standalone crate in a `day-*` directory. It is built in
+// release mode, then its binary is run on its input file.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Days drawing a grid, with what the rendering shows
+pub const GRID_DAYS: [(&str, &str); 4] = [
+    ("day-10", "loop"),
+    ("day-16", "energized tiles"),
+    ("day-17", "path"),
+    ("day-18-1", "lagoon"),
+];
+
+/// Output lines giving the answers of each day, in output order: `{}` is
+/// an answer, `{_}` a number which is not one. Other lines are ignored.
+const ANSWER_PATTERNS: [(&str, &[&str]); 22] = [
+    (
+        "day-01",
+        &["result (digits): {}", "result (spelled digits): {}"],
+    ),
+    (
+        "day-02",
+        &[
+            "result_first_half: {}",
+            "result_second_half (sum of powers of each cube set): {}",
+        ],
+    ),
+    (
+        "day-03",
+        &["Sum of parts (1st half) {}", "Product sum  (2nd half) {}"],
+    ),
+    ("day-04", &["1st half: {}", "2nd half: {}"]),
+    (
+        "day-05",
+        &[
+            "lowest location (seeds as values): {}",
+            "lowest location (seeds as ranges): {}",
+        ],
+    ),
+    (
+        "day-06",
+        &["result for 1st star is {}", "result for 2st star is {}"],
+    ),
+    ("day-07", &["result {}", "result with joker {}"]),
+    ("day-08", &["count {}"]),
+    ("day-09", &["Sum is ({}, {})"]),
+    ("day-10", &["{}"]),
+    ("day-11", &["Sum (x{_}) is {}"]),
+    ("day-12", &["Sum1: {}", "Sum2: {}"]),
+    ("day-13", &["Sum (smudge {_}): {}"]),
+    ("day-14", &["Score: {}", "Score {}"]),
+    ("day-15", &["sum: {}", "Part 2: {}"]),
+    ("day-16", &["part 1 {}", "Part 2 : {}"]),
+    ("day-17", &["Some({})"]),
+    ("day-18-1", &["Part 1: {}"]),
+    ("day-18-2", &["Part 2: {}"]),
+    ("day-19", &["Part 1: {}", "Part 2: {}"]),
+    ("day-20", &["Part 1 : {}", "Part 2 : {}"]),
+    ("day-21", &["Count = {}"]),
+];
+
+pub struct Day {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+pub enum Status {
+    BuildFailed(String),
+    RunFailed(String),
+    TimedOut,
+    // expected answers not given by the output
+    Invalid(Vec<String>),
+    Valid,
+    // no expected answers recorded for the day
+    Unchecked,
+}
+
+pub struct Outcome {
+    pub status: Status,
+    pub output: String,
+    pub answers: Vec<String>,
+    pub elapsed: Duration,
+    pub rendering: Option<String>,
+}
+
+/// List the `day-*` crates under `root`, sorted by name
+pub fn discover(root: &Path) -> std::io::Result<Vec<Day>> {
+    let mut days = fs::read_dir(fs::canonicalize(root)?)?
+        .map_while(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|dir| dir.join("Cargo.toml").is_file())
+        .filter_map(|dir| {
+            let name = dir.file_name()?.to_str()?.to_string();
+            name.starts_with("day-").then_some(Day { name, dir })
+        })
+        .collect::<Vec<Day>>();
+    days.sort_by(|d1, d2| d1.name.cmp(&d2.name));
+    Ok(days)
+}
+
+// The binary is named after the package
+fn package_name(day: &Day) -> std::io::Result<String> {
+    let manifest = fs::read_to_string(day.dir.join("Cargo.toml"))?;
+    manifest
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(key, _)| key.trim() == "name")
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        .ok_or(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{}: no package name", day.name),
+        ))
+}
+
+// Expected answers, one per line, in an optional `expected.txt`
+fn expected_answers(day: &Day) -> Option<Vec<String>> {
+    fs::read_to_string(day.dir.join("expected.txt"))
+        .ok()
+        .map(|content| {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+                .collect()
+        })
+}
+
+// Answers in `line` when it matches `pattern`
+fn match_line(mut pattern: &str, line: &str) -> Option<Vec<String>> {
+    let mut answers = Vec::new();
+    let mut rest = line.trim_end();
+    loop {
+        let hole = pattern.find('{');
+        rest = rest.strip_prefix(&pattern[..hole.unwrap_or(pattern.len())])?;
+        let Some(hole) = hole else {
+            return rest.is_empty().then_some(answers);
+        };
+        pattern = &pattern[hole..];
+
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| !(c.is_ascii_digit() || i == 0 && c == '-'))
+            .map_or(rest.len(), |(i, _)| i);
+        let (number, after) = rest.split_at(len);
+        if !number.bytes().any(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        match pattern.strip_prefix("{}") {
+            Some(after_hole) => {
+                answers.push(number.to_string());
+                pattern = after_hole;
+            }
+            None => pattern = pattern.strip_prefix("{_}")?,
+        }
+        rest = after;
+    }
+}
+
+/// Answers of a day found in its `output`, in output order
+pub fn answers(day: &Day, output: &str) -> Vec<String> {
+    let patterns = ANSWER_PATTERNS
+        .iter()
+        .find(|(name, _)| *name == day.name)
+        .map_or(&[][..], |(_, patterns)| patterns);
+    output
+        .lines()
+        .flat_map(|line| {
+            patterns
+                .iter()
+                .find_map(|pattern| match_line(pattern, line))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn validate(day: &Day, answers: &[String]) -> Status {
+    match expected_answers(day) {
+        None => Status::Unchecked,
+        Some(expected) => {
+            let missing = expected
+                .into_iter()
+                .enumerate()
+                .filter(|(i, answer)| answers.get(*i) != Some(answer))
+                .map(|(_, answer)| answer)
+                .collect::<Vec<String>>();
+            match missing.is_empty() {
+                true => Status::Valid,
+                false => Status::Invalid(missing),
+            }
+        }
+    }
+}
+
+// Collect everything written to a pipe, without blocking the caller
+fn read_in_background<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut content = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut content);
+        }
+        String::from_utf8_lossy(&content).into_owned()
+    })
+}
+
+/// Build and run one day on `input`, killing it after `timeout`. When `render` is
+/// given, the day is asked to draw its grid as SVG in this file, and the
+/// SVG is kept in the outcome.
+pub fn run(
+    day: &Day,
+    input: &Path,
+    render: Option<&Path>,
+    timeout: Duration,
+) -> std::io::Result<Outcome> {
+    let build = Command::new("cargo")
+        .args(["build", "--release", "--quiet"])
+        .current_dir(&day.dir)
+        .output()?;
+    if !build.status.success() {
+        return Ok(Outcome {
+            status: Status::BuildFailed(String::from_utf8_lossy(&build.stderr).into_owned()),
+            output: String::new(),
+            answers: Vec::new(),
+            elapsed: Duration::ZERO,
+            rendering: None,
+        });
+    }
+
+    let binary = day.dir.join("target/release").join(package_name(day)?);
+    let mut command = Command::new(binary);
+    command
+        .arg("-i")
+        .arg(input)
+        .current_dir(&day.dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(filename) = render {
+        // Never pick a rendering left by a previous run
+        let _ = fs::remove_file(filename);
+        command.arg("--render").arg(filename);
+    }
+
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let id = child.id();
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    // The end of the child is timed by a thread blocked waiting for it
+    let (sender, receiver) = mpsc::channel();
+    let waiter = thread::spawn(move || {
+        let exit = child.wait();
+        let _ = sender.send(start.elapsed());
+        exit
+    });
+    let finished = receiver.recv_timeout(timeout).ok();
+    if finished.is_none() {
+        // The child is not reaped before the waiter returns, so its id
+        // cannot have been reused yet
+        Command::new("kill")
+            .arg("-KILL")
+            .arg(id.to_string())
+            .status()?;
+    }
+    let exit = waiter.join().expect("waiter thread panicked")?;
+    let elapsed = finished.unwrap_or_else(|| start.elapsed());
+
+    let output = stdout.join().unwrap_or_default();
+    let errors = stderr.join().unwrap_or_default();
+    let answers = answers(day, &output);
+    let status = match finished {
+        None => Status::TimedOut,
+        Some(_) if exit.success() => validate(day, &answers),
+        Some(_) => Status::RunFailed(errors),
+    };
+    let rendering = render.and_then(|filename| fs::read_to_string(filename).ok());
+
+    Ok(Outcome {
+        status,
+        output,
+        answers,
+        elapsed,
+        rendering,
+    })
+}
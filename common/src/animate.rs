@@ -0,0 +1,140 @@
+// Terminal animation of step by step simulations.
+//
+// The animation takes over the terminal (alternate screen, raw mode) and
+// redraws every frame in place. Keys:
+//   space       pause / resume
+//   n, right    next frame, while paused
+//   + / -       faster / slower
+//   q, esc      stop animating, the simulation goes on without display
+//   ctrl-c      abort the program
+
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style, terminal};
+
+const HELP: &str = "[space] pause  [n] step  [+/-] speed  [q] quit";
+
+pub struct Animation {
+    delay: Duration,
+    paused: bool,
+    active: bool,
+    frame: usize,
+}
+
+impl Animation {
+    /// Take over the terminal, frames are separated by `delay_ms` milliseconds.
+    pub fn start(delay_ms: u64) -> std::io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(Animation {
+            delay: Duration::from_millis(delay_ms),
+            paused: false,
+            active: true,
+            frame: 0,
+        })
+    }
+
+    /// Redraw the screen with `lines` under a `title` status line,
+    /// then wait for the frame delay (or for a key while paused).
+    pub fn frame<I, S>(&mut self, title: &str, lines: I) -> std::io::Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        if !self.active {
+            return Ok(());
+        }
+        self.frame += 1;
+
+        let (cols, rows) = terminal::size()?;
+        let mut out = stdout().lock();
+        queue!(out, cursor::MoveTo(0, 0))?;
+
+        let status = format!(
+            "{} | frame {} | {} ms {}| {}",
+            title,
+            self.frame,
+            self.delay.as_millis(),
+            if self.paused { "| paused " } else { "" },
+            HELP
+        );
+        let status = status.chars().take(cols as usize).collect::<String>();
+        queue!(
+            out,
+            style::Print(status),
+            terminal::Clear(terminal::ClearType::UntilNewLine)
+        )?;
+
+        // Keep the status line, crop what does not fit in the terminal
+        for line in lines.into_iter().take(rows.saturating_sub(1) as usize) {
+            let line = line
+                .as_ref()
+                .chars()
+                .take(cols as usize)
+                .collect::<String>();
+            queue!(
+                out,
+                style::Print("\r\n"),
+                style::Print(line),
+                terminal::Clear(terminal::ClearType::UntilNewLine)
+            )?;
+        }
+        queue!(out, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+        out.flush()?;
+        drop(out);
+
+        self.wait()
+    }
+
+    fn wait(&mut self) -> std::io::Result<()> {
+        let start = Instant::now();
+        loop {
+            let timeout = match self.paused {
+                true => Duration::from_secs(3600),
+                false => self.delay.saturating_sub(start.elapsed()),
+            };
+            if !event::poll(timeout)? {
+                if self.paused {
+                    continue;
+                }
+                return Ok(());
+            }
+
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.stop()?;
+                    std::process::exit(130);
+                }
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                KeyCode::Char('n') | KeyCode::Right if self.paused => return Ok(()),
+                KeyCode::Char('+') => self.delay /= 2,
+                KeyCode::Char('-') => self.delay = (self.delay * 2).max(Duration::from_millis(1)),
+                KeyCode::Char('q') | KeyCode::Esc => return self.stop(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Give the terminal back, following frames are ignored
+    pub fn stop(&mut self) -> std::io::Result<()> {
+        if self.active {
+            self.active = false;
+            execute!(stdout(), cursor::Show, terminal::LeaveAlternateScreen)?;
+            terminal::disable_raw_mode()?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Animation {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
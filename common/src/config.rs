@@ -0,0 +1,117 @@
+// Project configuration, read from `aoc.toml`.
+//
+// The file is looked up in the starting directory, then in its parents.
+// Each day reads its tunable parameters from its own table, e.g.
+//
+//   [day-21]
+//   steps = 64
+//
+// and any of them can be overridden on the command line with `key=value`.
+// Missing file, table or key: the default of the day applies.
+
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+
+pub const FILENAME: &str = "aoc.toml";
+
+pub struct Config {
+    // directory containing the configuration file
+    root: PathBuf,
+    table: toml::Table,
+}
+
+/// Parameters of one day
+pub struct Params {
+    day: String,
+    table: toml::Table,
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+impl Config {
+    /// Load `aoc.toml` from `from` or its closest parent.
+    /// Without configuration file, everything keeps its default.
+    pub fn find(from: &Path) -> std::io::Result<Self> {
+        let from = std::fs::canonicalize(from)?;
+        match from.ancestors().find(|dir| dir.join(FILENAME).is_file()) {
+            Some(dir) => Config::load(&dir.join(FILENAME)),
+            None => Ok(Config {
+                root: from,
+                table: toml::Table::new(),
+            }),
+        }
+    }
+
+    pub fn load(filename: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(filename)?;
+        let table = content
+            .parse::<toml::Table>()
+            .map_err(|e| invalid_data(format!("{}: {}", filename.display(), e)))?;
+        let root = filename
+            .parent()
+            .map_or(PathBuf::from("."), Path::to_path_buf);
+        Ok(Config { root, table })
+    }
+
+    /// Directory `key` of the `[paths]` table, relative to the configuration file
+    pub fn dir(&self, key: &str, default: &str) -> PathBuf {
+        let dir = self
+            .table
+            .get("paths")
+            .and_then(|paths| paths.get(key))
+            .and_then(|dir| dir.as_str())
+            .unwrap_or(default);
+        self.root.join(dir)
+    }
+
+    /// Parameters of `day`, with the `key=value` `overrides` applied
+    pub fn params(&self, day: &str, overrides: &[String]) -> std::io::Result<Params> {
+        let mut table = match self.table.get(day) {
+            Some(toml::Value::Table(table)) => table.clone(),
+            Some(_) => return Err(invalid_data(format!("[{}] should be a table", day))),
+            None => toml::Table::new(),
+        };
+
+        for assignment in overrides {
+            let (key, value) = assignment
+                .split_once('=')
+                .ok_or(invalid_data(format!("{}: expected key=value", assignment)))?;
+            // Values are TOML (numbers, arrays, quoted strings ...),
+            // anything else is taken as a plain string
+            let value = format!("value = {}", value)
+                .parse::<toml::Table>()
+                .ok()
+                .and_then(|mut t| t.remove("value"))
+                .unwrap_or(toml::Value::String(value.to_string()));
+            table.insert(key.trim().to_string(), value);
+        }
+
+        Ok(Params {
+            day: day.to_string(),
+            table,
+        })
+    }
+}
+
+/// Shortcut for the days: parameters of `day` from the `aoc.toml`
+/// closest to the current directory
+pub fn day_params(day: &str, overrides: &[String]) -> std::io::Result<Params> {
+    Config::find(&std::env::current_dir()?)?.params(day, overrides)
+}
+
+impl Params {
+    /// Value of `key`, or `default` when it is not configured
+    pub fn get<T: DeserializeOwned>(&self, key: &str, default: T) -> std::io::Result<T> {
+        match self.table.get(key) {
+            Some(value) => value
+                .clone()
+                .try_into()
+                .map_err(|e| invalid_data(format!("[{}] {}: {}", self.day, key, e))),
+            None => Ok(default),
+        }
+    }
+}
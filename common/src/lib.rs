@@ -0,0 +1,7 @@
+// Helpers shared by the daily solvers.
+// Each day references this crate with a path dependency.
+
+pub mod animate;
+pub mod config;
+pub mod par;
+pub mod render;
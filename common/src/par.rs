@@ -0,0 +1,30 @@
+// Data-parallel execution of independent work units.
+//
+// All the solvers share one global rayon pool, sized once from the
+// `--threads` command line option (all cores when not given).
+// Results are always returned in the order of the input units, so the
+// output is the same whatever the number of threads.
+
+use rayon::prelude::*;
+
+/// Size the global thread pool. `None` keeps one thread per core.
+/// Must be called once, before any parallel work.
+pub fn init(threads: Option<usize>) {
+    if let Some(n) = threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build_global()
+            .expect("thread pool already initialized");
+    }
+}
+
+/// Apply `f` to every unit, in parallel.
+/// The result vector follows the order of `units`.
+pub fn map<T, R, F>(units: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    units.par_iter().map(f).collect()
+}
@@ -0,0 +1,165 @@
+// Image export of 2D grids.
+//
+// A grid is described by its size and a function returning how each
+// cell is drawn. The output format is chosen from the file extension:
+// `.png` for a bitmap, `.svg` for a vector image.
+
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+use std::path::Path;
+
+pub type Rgb = (u8, u8, u8);
+
+/// Size of one cell, in pixels (PNG) or user units (SVG)
+const CELL_SIZE: usize = 4;
+
+/// How a single cell is drawn: a background color filling the cell,
+/// and an optional overlay drawn as a smaller centered square.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Cell {
+    pub fill: Rgb,
+    pub overlay: Option<Rgb>,
+}
+
+impl Cell {
+    pub fn fill(color: Rgb) -> Self {
+        Cell {
+            fill: color,
+            overlay: None,
+        }
+    }
+
+    pub fn with_overlay(self, color: Rgb) -> Self {
+        Cell {
+            fill: self.fill,
+            overlay: Some(color),
+        }
+    }
+}
+
+/// Render a `width` x `height` grid to `filename`.
+/// `cell` is called with the (row, column) of every cell.
+pub fn render<F>(filename: &str, width: usize, height: usize, cell: F) -> std::io::Result<()>
+where
+    F: Fn(usize, usize) -> Cell,
+{
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    match extension.as_deref() {
+        Some("png") => render_png(filename, width, height, cell),
+        Some("svg") => render_svg(filename, width, height, cell),
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "{}: unsupported image format (expected .png or .svg)",
+                filename
+            ),
+        )),
+    }
+}
+
+// The overlay covers the center of the cell, leaving a 1 unit border
+fn in_overlay(ofs: usize) -> bool {
+    ofs > 0 && ofs < CELL_SIZE - 1
+}
+
+fn render_png<F>(filename: &str, width: usize, height: usize, cell: F) -> std::io::Result<()>
+where
+    F: Fn(usize, usize) -> Cell,
+{
+    let img_width = width * CELL_SIZE;
+    let img_height = height * CELL_SIZE;
+
+    let mut data = Vec::<u8>::with_capacity(img_width * img_height * 3);
+    for y in 0..height {
+        let row = (0..width).map(|x| cell(y, x)).collect::<Vec<Cell>>();
+        for dy in 0..CELL_SIZE {
+            for c in &row {
+                for dx in 0..CELL_SIZE {
+                    let color = match c.overlay {
+                        Some(overlay) if in_overlay(dy) && in_overlay(dx) => overlay,
+                        _ => c.fill,
+                    };
+                    data.extend([color.0, color.1, color.2]);
+                }
+            }
+        }
+    }
+
+    let file = File::create(filename)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), img_width as u32, img_height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&data))
+        .map_err(Error::other)
+}
+
+fn hex(color: &Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+fn render_svg<F>(filename: &str, width: usize, height: usize, cell: F) -> std::io::Result<()>
+where
+    F: Fn(usize, usize) -> Cell,
+{
+    let mut f = BufWriter::new(File::create(filename)?);
+    write_svg(&mut f, width, height, cell)?;
+    f.flush()
+}
+
+/// Write the SVG document of a grid to any output.
+/// Consecutive cells of the same color on a row are merged into a
+/// single rectangle to keep the document small.
+pub fn write_svg<W, F>(out: &mut W, width: usize, height: usize, cell: F) -> std::io::Result<()>
+where
+    W: Write,
+    F: Fn(usize, usize) -> Cell,
+{
+    let (w, h) = (width * CELL_SIZE, height * CELL_SIZE);
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" shape-rendering="crispEdges">"#
+    )?;
+
+    let inner = CELL_SIZE - 2;
+    for y in 0..height {
+        let row = (0..width).map(|x| cell(y, x)).collect::<Vec<Cell>>();
+
+        let mut x = 0;
+        while x < width {
+            let run = row[x..]
+                .iter()
+                .take_while(|c| c.fill == row[x].fill)
+                .count();
+            writeln!(
+                out,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                x * CELL_SIZE,
+                y * CELL_SIZE,
+                run * CELL_SIZE,
+                CELL_SIZE,
+                hex(&row[x].fill)
+            )?;
+            x += run;
+        }
+
+        for (x, c) in row.iter().enumerate() {
+            if let Some(overlay) = c.overlay {
+                writeln!(
+                    out,
+                    r#"<rect x="{}" y="{}" width="{inner}" height="{inner}" fill="{}"/>"#,
+                    x * CELL_SIZE + 1,
+                    y * CELL_SIZE + 1,
+                    hex(&overlay)
+                )?;
+            }
+        }
+    }
+
+    writeln!(out, "</svg>")
+}
@@ -0,0 +1,202 @@
+use std::fs::File;
+use std::io::{prelude::*, BufReader};
+
+use aho_corasick::AhoCorasick;
+use aoc_common::par;
+use clap::{Parser, ValueEnum};
+
+mod scanner;
+mod vocabulary;
+
+use scanner::Scanner;
+use vocabulary::Vocabulary;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// file to parse
+    #[arg(short, long, required_unless_present = "generate")]
+    input: Option<String>,
+
+    /// word list replacing the English spelled digits (one "word value" per line)
+    #[arg(short, long)]
+    words: Option<String>,
+
+    /// what to do with a line without any digit
+    #[arg(short, long, value_enum, default_value_t = Missing::Error)]
+    missing: Missing,
+
+    /// write the calibration of every line as CSV ("-" for stdout)
+    #[arg(short, long)]
+    report: Option<String>,
+
+    /// memory-mapped parallel scan, for very large files
+    #[arg(short, long, conflicts_with = "report")]
+    fast: bool,
+
+    /// number of worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
+
+    /// write random lines to a new file, then exit
+    #[arg(long, value_name = "FILE")]
+    generate: Option<String>,
+
+    /// number of lines to generate
+    #[arg(long, requires = "generate", default_value_t = 1_000_000)]
+    lines: usize,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Missing {
+    /// stop with an error
+    Error,
+    /// ignore the line
+    Skip,
+    /// count the line as 0
+    Zero,
+}
+
+// A token found in a line, `pos` is its character offset
+struct Token {
+    text: String,
+    pos: usize,
+}
+
+struct Calibration {
+    first: Token,
+    last: Token,
+    value: u32,
+}
+
+// Matches the tokens of a vocabulary, and gives back their value
+struct Engine {
+    ac: AhoCorasick,
+    values: Vec<u32>,
+}
+
+impl Engine {
+    fn new(vocabulary: &Vocabulary) -> Self {
+        Engine {
+            ac: AhoCorasick::new(vocabulary.patterns()).unwrap(),
+            values: vocabulary.tokens.iter().map(|(_, value)| *value).collect(),
+        }
+    }
+
+    // First digit * 10 + last digit of the line, None without any digit.
+    // Tokens may overlap, e.g. "twone" is 2 then 1
+    fn calibration(&self, line: &str) -> Option<Calibration> {
+        let mut iter = self.ac.find_overlapping_iter(line);
+        let first = iter.next()?;
+        let last = iter.last().unwrap_or(first);
+
+        let token = |m: &aho_corasick::Match| Token {
+            text: line[m.start()..m.end()].to_string(),
+            pos: line[..m.start()].chars().count(),
+        };
+        let first_val = self.values[first.pattern().as_usize()];
+        let last_val = self.values[last.pattern().as_usize()];
+        Some(Calibration {
+            first: token(&first),
+            last: token(&last),
+            value: first_val * 10 + last_val,
+        })
+    }
+}
+
+fn no_digit(line: usize, part: usize) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("line {}: no digit for part {}", line, part),
+    )
+}
+
+fn fast(args: &Args, input: &str, vocabularies: &[Vocabulary; 2]) -> std::io::Result<()> {
+    let data = scanner::map(input)?;
+    let data = data.as_deref().unwrap_or_default();
+
+    let mut result = [0, 0];
+    for (part, (sum, vocabulary)) in result.iter_mut().zip(vocabularies).enumerate() {
+        let totals = Scanner::new(vocabulary).scan(data);
+        if let (Some(line), Missing::Error) = (totals.missing, args.missing) {
+            return Err(no_digit(line, part + 1));
+        }
+        // skipped lines and lines counted as 0 give the same sum
+        *sum = totals.sum;
+    }
+
+    println!("result (digits): {}", result[0]);
+    println!("result (spelled digits): {}", result[1]);
+
+    Ok(())
+}
+
+fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+
+    let spelled = match &args.words {
+        Some(filename) => Vocabulary::from_file(filename)?,
+        None => Vocabulary::english(),
+    };
+    if let Some(filename) = &args.generate {
+        return scanner::generate(filename, args.lines);
+    }
+    // required by clap when not generating
+    let input = args.input.as_deref().unwrap();
+    if args.fast {
+        par::init(args.threads);
+        return fast(&args, input, &[Vocabulary::digits(), spelled]);
+    }
+
+    let engines = [Engine::new(&Vocabulary::digits()), Engine::new(&spelled)];
+
+    let file = File::open(input)?;
+    let reader = BufReader::new(file);
+
+    let mut report: Option<Box<dyn Write>> = match args.report.as_deref() {
+        None => None,
+        Some("-") => Some(Box::new(std::io::stdout())),
+        Some(filename) => Some(Box::new(std::io::BufWriter::new(File::create(filename)?))),
+    };
+    if let Some(out) = &mut report {
+        writeln!(out, "line,part,first,first_pos,last,last_pos,value")?;
+    }
+
+    let mut result = [0, 0];
+    for (nb, line) in reader.lines().enumerate() {
+        let line = line?;
+        let nb = nb + 1;
+
+        for (part, (sum, engine)) in result.iter_mut().zip(&engines).enumerate() {
+            let row = match (engine.calibration(&line), args.missing) {
+                (Some(c), _) => {
+                    *sum += c.value;
+                    format!(
+                        "{},{},{},{},{},{},{}",
+                        nb,
+                        part + 1,
+                        c.first.text,
+                        c.first.pos,
+                        c.last.text,
+                        c.last.pos,
+                        c.value
+                    )
+                }
+                (None, Missing::Error) => return Err(no_digit(nb, part + 1)),
+                (None, Missing::Skip) => continue,
+                (None, Missing::Zero) => format!("{},{},,,,,0", nb, part + 1),
+            };
+            if let Some(out) = &mut report {
+                writeln!(out, "{}", row)?;
+            }
+        }
+    }
+    if let Some(out) = &mut report {
+        out.flush()?;
+    }
+
+    println!("result (digits): {}", result[0]);
+    println!("result (spelled digits): {}", result[1]);
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+// High-throughput scanner for large calibration documents.
+//
+// The input is memory-mapped and split into chunks of whole lines that
+// are scanned in parallel, without any allocation per line.
+// In each line, the first token is searched forward from the start, and
+// the last one by running an automaton of the reversed tokens on a copy
+// of the line reversed into a scratch buffer reused from line to line.
+
+use std::fs::File;
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use aoc_common::par;
+use memmap2::Mmap;
+
+use crate::vocabulary::Vocabulary;
+
+// Approximate size of a chunk, extended up to the next end of line
+const CHUNK_SIZE: usize = 1 << 22;
+
+pub struct Scanner {
+    forward: AhoCorasick,
+    reverse: AhoCorasick,
+    values: Vec<u32>,
+}
+
+/// Result of the scan of some lines
+#[derive(Default, Clone, Copy)]
+pub struct Totals {
+    pub lines: usize,
+    pub sum: u64,
+    /// first line without any digit, counted from 1
+    pub missing: Option<usize>,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let patterns = vocabulary.patterns();
+        let reversed = patterns
+            .iter()
+            .map(|p| p.bytes().rev().collect::<Vec<u8>>())
+            .collect::<Vec<Vec<u8>>>();
+        let build = |kind| AhoCorasick::builder().match_kind(kind).to_owned();
+        Scanner {
+            forward: build(MatchKind::LeftmostFirst).build(patterns).unwrap(),
+            reverse: build(MatchKind::LeftmostFirst).build(reversed).unwrap(),
+            values: vocabulary.tokens.iter().map(|(_, value)| *value).collect(),
+        }
+    }
+
+    // Calibration value of a line, `reversed` is a scratch buffer
+    fn calibration(&self, line: &[u8], reversed: &mut Vec<u8>) -> Option<u32> {
+        let first = self.forward.find(line)?;
+        reversed.clear();
+        reversed.extend(line.iter().rev());
+        let last = self.reverse.find(&reversed[..]).unwrap_or(first);
+        Some(self.values[first.pattern().as_usize()] * 10 + self.values[last.pattern().as_usize()])
+    }
+
+    fn scan_chunk(&self, chunk: &[u8]) -> Totals {
+        let chunk = chunk.strip_suffix(b"\n").unwrap_or(chunk);
+        let mut reversed = Vec::new();
+        let mut totals = Totals::default();
+        for line in chunk.split(|&b| b == b'\n') {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            totals.lines += 1;
+            match self.calibration(line, &mut reversed) {
+                Some(value) => totals.sum += value as u64,
+                None => {
+                    totals.missing.get_or_insert(totals.lines);
+                }
+            }
+        }
+        totals
+    }
+
+    /// Scan all the lines of `data`, in parallel chunks
+    pub fn scan(&self, data: &[u8]) -> Totals {
+        let results = par::map(&chunks(data), |chunk| self.scan_chunk(chunk));
+
+        // Chunks are in file order: line numbers are shifted by the
+        // number of lines of the previous chunks
+        results.iter().fold(Totals::default(), |acc, t| Totals {
+            lines: acc.lines + t.lines,
+            sum: acc.sum + t.sum,
+            missing: acc.missing.or(t.missing.map(|nb| acc.lines + nb)),
+        })
+    }
+}
+
+// Split `data` into chunks of whole lines
+fn chunks(data: &[u8]) -> Vec<&[u8]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < data.len() {
+        let end = match data[(start + CHUNK_SIZE).min(data.len())..]
+            .iter()
+            .position(|&b| b == b'\n')
+        {
+            Some(pos) => start + CHUNK_SIZE + pos + 1,
+            None => data.len(),
+        };
+        chunks.push(&data[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+/// Memory-map a whole file. An empty file gives no mapping.
+pub fn map(filename: &str) -> std::io::Result<Option<Mmap>> {
+    let file = File::open(filename)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+    // The file must not be modified while it is scanned
+    unsafe { Mmap::map(&file) }.map(Some)
+}
+
+/// Write `lines` random calibration lines to a new file `filename`, to
+/// benchmark the scanner. An existing file is never overwritten.
+pub fn generate(filename: &str, lines: usize) -> std::io::Result<()> {
+    use std::io::Write;
+
+    const WORDS: [&str; 12] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "x", "q", "z",
+    ];
+    // xorshift, deterministic output for a given line count
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |n: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % n as u64) as usize
+    };
+
+    let mut out = std::io::BufWriter::new(File::create_new(filename)?);
+    let mut line = String::new();
+    for _ in 0..lines {
+        line.clear();
+        // at least one digit per line, anywhere among the words
+        let words = 2 + next(8);
+        let digit = next(words + 1);
+        for i in 0..=words {
+            if i == digit {
+                line.push(char::from(b'1' + next(9) as u8));
+            }
+            if i < words {
+                line.push_str(WORDS[next(WORDS.len())]);
+            }
+        }
+        writeln!(out, "{}", line)?;
+    }
+    out.flush()
+}
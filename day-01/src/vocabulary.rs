@@ -0,0 +1,81 @@
+// Tokens recognized as digits in a calibration line.
+//
+// The ten digits are always part of a vocabulary, spelled out digits
+// come from the built-in English list or from a word list file:
+//
+//   # French
+//   un 1
+//   deux 2
+//   ...
+
+use std::io::{Error, ErrorKind};
+
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub struct Vocabulary {
+    pub tokens: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Digits only, as in part 1
+    pub fn digits() -> Self {
+        Vocabulary {
+            tokens: (0..10).map(|d| (d.to_string(), d)).collect(),
+        }
+    }
+
+    /// Digits and English words
+    pub fn english() -> Self {
+        Vocabulary::digits().with_words(
+            ENGLISH
+                .iter()
+                .map(|(word, value)| (word.to_string(), *value)),
+        )
+    }
+
+    /// Digits and the words of a list file: one `word value` per line,
+    /// empty lines and lines starting with '#' are ignored
+    pub fn from_file(filename: &str) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(filename)?;
+        let words = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.split_once(char::is_whitespace)
+                    .and_then(|(word, value)| {
+                        let value = value.trim().parse::<u32>().ok()?;
+                        (value < 10).then_some((word.to_string(), value))
+                    })
+                    .ok_or(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("{}: invalid word '{}'", filename, line),
+                    ))
+            })
+            .collect::<std::io::Result<Vec<(String, u32)>>>()?;
+
+        Ok(Vocabulary::digits().with_words(words))
+    }
+
+    fn with_words<I: IntoIterator<Item = (String, u32)>>(mut self, words: I) -> Self {
+        self.tokens.extend(words);
+        self
+    }
+
+    pub fn patterns(&self) -> Vec<&str> {
+        self.tokens
+            .iter()
+            .map(|(token, _)| token.as_str())
+            .collect()
+    }
+}
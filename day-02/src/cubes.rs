@@ -0,0 +1,75 @@
+// Counts of cubes by color, for any set of colors.
+//
+// A set of cubes is written as in the games, e.g. "12 red, 13 green, 14 blue".
+// A color that is not listed counts 0.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{Error, ErrorKind};
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct CubeCnt(BTreeMap<String, i32>);
+
+impl CubeCnt {
+    /// Parse "<count> <color>" items, separated by commas or new lines
+    pub fn parse(text: &str) -> std::io::Result<Self> {
+        let mut cubes = CubeCnt::default();
+        for item in text.split([',', '\n']).map(str::trim) {
+            if item.is_empty() || item.starts_with('#') {
+                continue;
+            }
+            let (qte, color) = item
+                .split_once(char::is_whitespace)
+                .and_then(|(qte, color)| Some((qte.parse::<i32>().ok()?, color.trim())))
+                .ok_or(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid cubes '{}', expected <count> <color>", item),
+                ))?;
+            cubes.set(color, qte);
+        }
+        Ok(cubes)
+    }
+
+    pub fn get(&self, color: &str) -> i32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i32)> {
+        self.0.iter().map(|(color, qte)| (color.as_str(), *qte))
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    pub fn set(&mut self, color: &str, qte: i32) {
+        self.0.insert(color.to_string(), qte);
+    }
+
+    /// Number of cubes, all colors
+    pub fn total(&self) -> i32 {
+        self.0.values().sum()
+    }
+
+    /// Keep the max of the current and the new count of `color`
+    pub fn add_max(&mut self, color: &str, qte: i32) {
+        let cnt = self.0.entry(color.to_string()).or_insert(0);
+        *cnt = (*cnt).max(qte);
+    }
+
+    /// Product of the counts of all the colors of `palette`
+    pub fn power(&self, palette: &BTreeSet<&str>) -> i32 {
+        palette.iter().map(|color| self.get(color)).product()
+    }
+}
+
+// Same format as the games: "12 red, 13 green, 14 blue"
+impl fmt::Display for CubeCnt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let items = self
+            .iter()
+            .map(|(color, qte)| format!("{} {}", qte, color))
+            .collect::<Vec<String>>();
+        write!(f, "{}", items.join(", "))
+    }
+}
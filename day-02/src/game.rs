@@ -0,0 +1,70 @@
+// A game: the cubes revealed in each of its rounds.
+//
+//   Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+
+use std::io::{Error, ErrorKind};
+
+use crate::cubes::CubeCnt;
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: i32,
+    pub rounds: Vec<CubeCnt>,
+}
+
+/// Why a game is impossible with a bag: first color of a round above the bag
+#[derive(Debug)]
+pub struct Excess<'a> {
+    /// index of the round, counted from 1
+    pub round: usize,
+    pub color: &'a str,
+    pub revealed: i32,
+    pub available: i32,
+}
+
+impl Game {
+    pub fn parse(line: &str) -> std::io::Result<Self> {
+        let invalid = || Error::new(ErrorKind::InvalidData, format!("invalid game '{}'", line));
+        let (id, rounds) = line.split_once(':').ok_or_else(invalid)?;
+        let id = id
+            .trim()
+            .strip_prefix("Game ")
+            .and_then(|id| id.trim().parse::<i32>().ok())
+            .ok_or_else(invalid)?;
+        let rounds = rounds
+            .split(';')
+            .map(CubeCnt::parse)
+            .collect::<std::io::Result<Vec<CubeCnt>>>()?;
+        Ok(Game { id, rounds })
+    }
+
+    /// Minimal set of cubes for all the rounds
+    pub fn minimum(&self) -> CubeCnt {
+        let mut cubes = CubeCnt::default();
+        for round in &self.rounds {
+            for (color, qte) in round.iter() {
+                cubes.add_max(color, qte);
+            }
+        }
+        cubes
+    }
+
+    /// First round and color exceeding the bag, None if the game is possible
+    pub fn excess(&self, bag: &CubeCnt) -> Option<Excess<'_>> {
+        self.rounds.iter().enumerate().find_map(|(i, round)| {
+            round
+                .iter()
+                .find(|(color, qte)| *qte > bag.get(color))
+                .map(|(color, qte)| Excess {
+                    round: i + 1,
+                    color,
+                    revealed: qte,
+                    available: bag.get(color),
+                })
+        })
+    }
+
+    pub fn is_possible(&self, bag: &CubeCnt) -> bool {
+        self.excess(bag).is_none()
+    }
+}
@@ -0,0 +1,99 @@
+// Estimation of the bag contents from the recorded games.
+//
+// Each round is seen as a draw without replacement from the bag (the
+// cubes go back into the bag between two rounds), so the probability of
+// a round follows the multivariate hypergeometric law:
+//
+//   P(round) = prod(C(N_color, k_color)) / C(N, k)
+//
+// with N cubes in the bag and k cubes revealed in the round.
+
+use crate::cubes::CubeCnt;
+use crate::game::Game;
+
+// ln(n!) for all n up to a maximum
+struct LogFact(Vec<f64>);
+
+impl LogFact {
+    fn new(max: usize) -> Self {
+        let mut table = vec![0.0; max + 1];
+        for n in 1..=max {
+            table[n] = table[n - 1] + (n as f64).ln();
+        }
+        LogFact(table)
+    }
+
+    // ln(C(n, k)), -inf when k > n
+    fn choose(&self, n: i32, k: i32) -> f64 {
+        if k < 0 || k > n {
+            return f64::NEG_INFINITY;
+        }
+        let (n, k) = (n as usize, k as usize);
+        self.0[n] - self.0[k] - self.0[n - k]
+    }
+}
+
+/// Smallest bag making all the games possible
+pub fn minimal_bag(games: &[Game]) -> CubeCnt {
+    let mut bag = CubeCnt::default();
+    for game in games {
+        for (color, qte) in game.minimum().iter() {
+            bag.add_max(color, qte);
+        }
+    }
+    bag
+}
+
+/// Log-likelihood of all the games for a bag, -inf if one is impossible
+pub fn log_likelihood(games: &[Game], bag: &CubeCnt) -> f64 {
+    let total = bag.total();
+    let fact = LogFact::new(total.max(0) as usize);
+    games
+        .iter()
+        .flat_map(|game| &game.rounds)
+        .map(|round| {
+            let colors = round
+                .iter()
+                .map(|(color, qte)| fact.choose(bag.get(color), qte))
+                .sum::<f64>();
+            if colors == f64::NEG_INFINITY {
+                // avoids -inf - -inf when the round has more cubes than the bag
+                return colors;
+            }
+            colors - fact.choose(total, round.total())
+        })
+        .sum()
+}
+
+/// Bag of maximum likelihood, searched from the minimal bag by changing
+/// one color by one cube at a time while the likelihood increases.
+/// No color goes beyond `max_count` cubes: bigger bags with the same
+/// proportions are often more likely, the games mostly tell the proportions.
+pub fn most_likely(games: &[Game], max_count: i32) -> CubeCnt {
+    let mut bag = minimal_bag(games);
+    let mut best = log_likelihood(games, &bag);
+    loop {
+        let colors = bag.colors().map(str::to_string).collect::<Vec<String>>();
+        let neighbour = colors
+            .iter()
+            .flat_map(|color| [(color, 1), (color, -1)])
+            .filter_map(|(color, delta)| {
+                let qte = bag.get(color) + delta;
+                if qte < 0 || qte > max_count {
+                    return None;
+                }
+                let mut other = bag.clone();
+                other.set(color, qte);
+                let ll = log_likelihood(games, &other);
+                Some((other, ll))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b));
+        match neighbour {
+            Some((other, ll)) if ll > best => {
+                bag = other;
+                best = ll;
+            }
+            _ => return bag,
+        }
+    }
+}
@@ -1,8 +1,16 @@
+use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::config;
 use clap::Parser;
-use regex::Regex;
+
+mod cubes;
+mod game;
+mod infer;
+
+use cubes::CubeCnt;
+use game::Game;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -10,55 +18,136 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
-}
 
-#[derive(Default, Debug)]
-struct CubeCnt {
-    red: i32,
-    green: i32,
-    blue: i32,
+    /// bag contents, e.g. "12 red, 13 green, 14 blue"
+    #[arg(short, long, conflicts_with = "bag_file")]
+    bag: Option<String>,
+
+    /// file with the bag contents, one "<count> <color>" per line
+    #[arg(long)]
+    bag_file: Option<String>,
+
+    /// tell the round and color making each impossible game fail
+    #[arg(short, long)]
+    explain: bool,
+
+    /// list the games possible with another bag (can be repeated)
+    #[arg(short, long, value_name = "BAG")]
+    query: Vec<String>,
+
+    /// estimate the bag contents from the games, and compare with the
+    /// configured bag and the queried ones
+    #[arg(long)]
+    infer: bool,
+
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    let params = config::day_params("day-02", &args.params)?;
+
+    // Bag contents
+    let bag = match (&args.bag, &args.bag_file) {
+        (Some(bag), _) => bag.clone(),
+        (None, Some(filename)) => std::fs::read_to_string(filename)?,
+        (None, None) => params.get("bag", "12 red, 13 green, 14 blue".to_string())?,
+    };
+    let bag = CubeCnt::parse(&bag)?;
 
-    let file = File::open(args.input)?;
+    let file = File::open(&args.input)?;
     let reader = BufReader::new(file);
 
-    let re_start = Regex::new(r"Game ([0-9]+):").unwrap();
-    let re_revealed = Regex::new(r"([:;,]) *([0-9]+) +(green|red|blue)").unwrap();
+    let games = reader
+        .lines()
+        .map(|line| Game::parse(&line?))
+        .collect::<std::io::Result<Vec<Game>>>()?;
 
-    let mut result_first_half = 0;
-    let mut result_second_half = 0;
-    for line in reader.lines() {
-        let line = line?;
-
-        let mut cubes = CubeCnt::default();
-
-        let game = re_start
-            .captures(&line)
-            .unwrap()
-            .get(1)
-            .unwrap()
-            .as_str()
-            .parse::<i32>()
-            .unwrap();
-        let matches = re_revealed.captures_iter(&line);
-        for m in matches {
-            let (_, [_sep, qte, color]) = m.extract();
-            let qte = qte.parse::<i32>().unwrap();
-            match color {
-                "red" => cubes.red = cubes.red.max(qte),
-                "green" => cubes.green = cubes.green.max(qte),
-                "blue" => cubes.blue = cubes.blue.max(qte),
-                _ => panic!("unexpected color"),
+    // Minimal set of cubes of each game
+    let minimums = games.iter().map(Game::minimum).collect::<Vec<CubeCnt>>();
+
+    // The power of a game uses every color seen in the games, 0 for those
+    // it does not reveal
+    let palette = minimums
+        .iter()
+        .flat_map(CubeCnt::colors)
+        .collect::<BTreeSet<&str>>();
+
+    if args.explain {
+        for game in &games {
+            if let Some(excess) = game.excess(&bag) {
+                println!(
+                    "Game {}: round {} reveals {} {}, the bag has {}",
+                    game.id, excess.round, excess.revealed, excess.color, excess.available
+                );
             }
         }
-        if cubes.blue <= 14 && cubes.red <= 12 && cubes.green <= 13 {
-            result_first_half += game;
+    }
+
+    for query in &args.query {
+        let other = CubeCnt::parse(query)?;
+        let possible = games
+            .iter()
+            .filter(|game| game.is_possible(&other))
+            .map(|game| game.id.to_string())
+            .collect::<Vec<String>>();
+        println!(
+            "possible with {}: {} games [{}]",
+            query,
+            possible.len(),
+            possible.join(", ")
+        );
+    }
+
+    if args.infer {
+        let minimal = infer::minimal_bag(&games);
+        let max_count = params.get("max_count", 100)?;
+        let likely = infer::most_likely(&games, max_count);
+        if likely.iter().any(|(_, qte)| qte >= max_count) {
+            println!(
+                "most likely bag limited by max_count = {}: only the proportions are reliable",
+                max_count
+            );
+        }
+        let mut candidates = vec![
+            ("minimal".to_string(), minimal),
+            ("most likely".to_string(), likely),
+            ("bag".to_string(), bag.clone()),
+        ];
+        for query in &args.query {
+            candidates.push((query.clone(), CubeCnt::parse(query)?));
+        }
+
+        // Likelihoods are relative to the best candidate
+        let lls = candidates
+            .iter()
+            .map(|(_, cubes)| infer::log_likelihood(&games, cubes))
+            .collect::<Vec<f64>>();
+        let best = lls.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        println!("candidate | bag | possible games | log-likelihood | relative likelihood");
+        for ((name, cubes), ll) in candidates.iter().zip(&lls) {
+            let possible = games.iter().filter(|game| game.is_possible(cubes)).count();
+            println!(
+                "{} | {} | {}/{} | {:.2} | {:.3e}",
+                name,
+                cubes,
+                possible,
+                games.len(),
+                ll,
+                (ll - best).exp()
+            );
+        }
+    }
+
+    let mut result_first_half = 0;
+    let mut result_second_half = 0;
+    for (game, cubes) in games.iter().zip(&minimums) {
+        if game.is_possible(&bag) {
+            result_first_half += game.id;
         }
-        let power = cubes.blue * cubes.red * cubes.green;
-        result_second_half += power;
+        result_second_half += cubes.power(&palette);
     }
 
     println!("result_first_half: {}", result_first_half);
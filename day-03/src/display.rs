@@ -0,0 +1,60 @@
+// Colored rendering of the schematic in the terminal (ANSI escapes).
+//
+// Part numbers are green, other numbers dimmed, gears red with their
+// value listed at the end of the row, other symbols yellow.
+
+use crate::rules::Rule;
+use crate::schematic::Schematic;
+
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "\x1b[2m";
+const PART: &str = "\x1b[1;32m";
+const GEAR: &str = "\x1b[1;97;41m";
+const SYMBOL: &str = "\x1b[1;33m";
+
+pub fn print(schematic: &Schematic, rules: &[Rule]) {
+    let width = schematic.width;
+    if width == 0 {
+        return;
+    }
+
+    // Style of each cell, and gears listed per row
+    let mut styles = vec![DIM; schematic.grid.len()];
+    let mut notes = vec![Vec::new(); schematic.height];
+    for (n, number) in schematic.numbers.iter().enumerate() {
+        let style = if schematic.is_part(n) { PART } else { DIM };
+        for col in number.cols.clone() {
+            styles[number.row * width + col] = style;
+        }
+    }
+    for (symbol, gears) in schematic.symbols.iter().zip(schematic.gears(rules)) {
+        let pos = symbol.row * width + symbol.col;
+        styles[pos] = if gears.is_empty() { SYMBOL } else { GEAR };
+        for (rule, value) in gears {
+            notes[symbol.row].push(format!(
+                "col {} {}{}{} = {}",
+                symbol.col, GEAR, rule, RESET, value
+            ));
+        }
+    }
+
+    for (row, line) in schematic.grid.chunks(width).enumerate() {
+        let mut out = String::new();
+        let mut current = "";
+        for (col, &c) in line.iter().enumerate() {
+            let style = styles[row * width + col];
+            if style != current {
+                out.push_str(RESET);
+                out.push_str(style);
+                current = style;
+            }
+            out.push(c as char);
+        }
+        out.push_str(RESET);
+        if !notes[row].is_empty() {
+            out.push_str("  ");
+            out.push_str(&notes[row].join("  "));
+        }
+        println!("{}", out);
+    }
+}
@@ -0,0 +1,136 @@
+// Number <-> symbol adjacency graph, for debugging schematics.
+//
+// Numbers and symbols are the nodes, with their position in the grid,
+// an edge links a number to each symbol around it. Part numbers and the
+// symbols qualifying as gears (with the value given by their rule) are
+// flagged.
+
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+
+use serde_json::json;
+
+use crate::rules::Rule;
+use crate::schematic::Schematic;
+
+fn write_dot<W: Write>(out: &mut W, schematic: &Schematic, rules: &[Rule]) -> std::io::Result<()> {
+    writeln!(out, "graph schematic {{")?;
+    writeln!(out, "  node [fontname=monospace];")?;
+    // pos is in grid cells, for `neato -n`
+    for (n, number) in schematic.numbers.iter().enumerate() {
+        let color = if schematic.is_part(n) {
+            "green"
+        } else {
+            "gray"
+        };
+        writeln!(
+            out,
+            "  n{} [shape=box, label=\"{}\", color={}, part={}, row={}, col={}, pos=\"{},{}!\"];",
+            n,
+            number.value,
+            color,
+            schematic.is_part(n),
+            number.row,
+            number.cols.start,
+            number.cols.start * 20,
+            -(number.row as i64) * 20
+        )?;
+    }
+    for (s, (symbol, gears)) in schematic
+        .symbols
+        .iter()
+        .zip(schematic.gears(rules))
+        .enumerate()
+    {
+        let label = (symbol.symbol as char).escape_default().to_string();
+        let (label, color) = match gears.first() {
+            Some((_, value)) => (format!("{} {}", label, value), "red"),
+            None => (label, "black"),
+        };
+        writeln!(
+            out,
+            "  s{} [shape=circle, label=\"{}\", color={}, gear={}, row={}, col={}, pos=\"{},{}!\"];",
+            s,
+            label,
+            color,
+            !gears.is_empty(),
+            symbol.row,
+            symbol.col,
+            symbol.col * 20,
+            -(symbol.row as i64) * 20
+        )?;
+    }
+    for (n, symbols) in schematic.number_symbols.iter().enumerate() {
+        for s in symbols {
+            writeln!(out, "  n{} -- s{};", n, s)?;
+        }
+    }
+    writeln!(out, "}}")
+}
+
+fn to_json(schematic: &Schematic, rules: &[Rule]) -> serde_json::Value {
+    let numbers = schematic
+        .numbers
+        .iter()
+        .enumerate()
+        .map(|(n, number)| {
+            json!({
+                "id": n,
+                "value": number.value,
+                "row": number.row,
+                "cols": [number.cols.start, number.cols.end],
+                "part": schematic.is_part(n),
+                "symbols": schematic.number_symbols[n],
+            })
+        })
+        .collect::<Vec<_>>();
+    let symbols = schematic
+        .symbols
+        .iter()
+        .zip(schematic.gears(rules))
+        .enumerate()
+        .map(|(s, (symbol, gears))| {
+            let gears = gears
+                .iter()
+                .map(|(rule, value)| json!({ "rule": rule.to_string(), "value": value }))
+                .collect::<Vec<_>>();
+            json!({
+                "id": s,
+                "symbol": (symbol.symbol as char).to_string(),
+                "row": symbol.row,
+                "col": symbol.col,
+                "numbers": schematic.symbol_numbers[s],
+                "gears": gears,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "width": schematic.width,
+        "height": schematic.height,
+        "numbers": numbers,
+        "symbols": symbols,
+    })
+}
+
+/// Write the graph to `filename`, as DOT (.dot, .gv) or JSON (.json)
+pub fn export(filename: &str, schematic: &Schematic, rules: &[Rule]) -> std::io::Result<()> {
+    let json = match filename.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("dot") | Some("gv") => false,
+        Some("json") => true,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{}: expected a .dot, .gv or .json file", filename),
+            ))
+        }
+    };
+
+    let mut out = BufWriter::new(File::create(filename)?);
+    if json {
+        serde_json::to_writer_pretty(&mut out, &to_json(schematic, rules))?;
+        writeln!(out)?;
+    } else {
+        write_dot(&mut out, schematic, rules)?;
+    }
+    out.flush()
+}
@@ -1,10 +1,15 @@
-use core::ops::Range;
-use std::collections::HashMap;
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::collections::{BTreeMap, BTreeSet};
 
+use aoc_common::config;
 use clap::Parser;
-use regex::Regex;
+
+mod display;
+mod graph;
+mod rules;
+mod schematic;
+
+use rules::Rule;
+use schematic::Schematic;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -12,131 +17,99 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
-}
 
-struct Gear {
-    nb_parts: u32,
-    product: u32,
-}
+    /// gear rule, e.g. "*>=2:product" (can be repeated, replaces the configured rules)
+    #[arg(short, long)]
+    rule: Vec<String>,
 
-fn is_symbol(c: &char) -> bool {
-    (*c != '.') && !c.is_numeric()
-}
+    /// print, for each symbol, how many part numbers touch it
+    #[arg(short, long)]
+    stats: bool,
 
-fn have_symbol(range: &Range<usize>, prev: &str, line: &str, next: &str) -> bool {
-    if range.start > 0 {
-        if is_symbol(&prev.chars().nth(range.start - 1).unwrap())
-            || is_symbol(&line.chars().nth(range.start - 1).unwrap())
-            || is_symbol(&next.chars().nth(range.start - 1).unwrap())
-        {
-            return true;
-        }
-    }
-    for i in range.start..range.end {
-        if is_symbol(&prev.chars().nth(i).unwrap()) || is_symbol(&next.chars().nth(i).unwrap()) {
-            return true;
-        }
-    }
+    /// export the number/symbol adjacency graph (.dot, .gv or .json)
+    #[arg(short, long)]
+    graph: Option<String>,
 
-    range.end < line.len()
-        && (is_symbol(&prev.chars().nth(range.end).unwrap())
-            || is_symbol(&line.chars().nth(range.end).unwrap())
-            || is_symbol(&next.chars().nth(range.end).unwrap()))
-}
+    /// print the schematic with colors: parts, other numbers, gears and their value
+    #[arg(long)]
+    render: bool,
 
-fn add_gear(gears: &mut Vec<(usize, usize)>, l: usize, c: usize, line: &str) {
-    if line.chars().nth(c).unwrap() == '*' {
-        gears.push((l, c));
-    }
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
-fn have_gears(
-    range: &Range<usize>,
-    l: usize,
-    prev: &str,
-    line: &str,
-    next: &str,
-) -> Vec<(usize, usize)> {
-    let mut gears = Vec::<(usize, usize)>::new();
-    if range.start > 0 {
-        add_gear(&mut gears, l - 1, range.start - 1, prev);
-        add_gear(&mut gears, l, range.start - 1, line);
-        add_gear(&mut gears, l + 1, range.start - 1, next);
-    }
-    for i in range.start..range.end {
-        add_gear(&mut gears, l - 1, i, prev);
-        add_gear(&mut gears, l + 1, i, next);
-    }
-    if range.end < line.len() {
-        add_gear(&mut gears, l - 1, range.end, prev);
-        add_gear(&mut gears, l, range.end, line);
-        add_gear(&mut gears, l + 1, range.end, next);
+// Occurrences of each symbol, and the distinct part numbers around them
+fn print_stats(schematic: &Schematic) {
+    let mut stats = BTreeMap::<u8, (usize, BTreeSet<usize>, BTreeMap<usize, usize>)>::new();
+    for (symbol, numbers) in schematic.symbols.iter().zip(&schematic.symbol_numbers) {
+        let (count, parts, histogram) = stats.entry(symbol.symbol).or_default();
+        *count += 1;
+        parts.extend(numbers);
+        *histogram.entry(numbers.len()).or_default() += 1;
     }
-    gears
-}
 
-fn process_line(
-    re: &Regex,
-    l: usize,
-    gears: &mut HashMap<(usize, usize), Gear>,
-    connected_sum: &mut u32,
-    prev: &str,
-    line: &str,
-    next: &str,
-) {
-    for cap in re.captures_iter(line) {
-        let id = cap.get(0).unwrap();
-        let range = id.range();
-        let part_id = id.as_str().parse::<u32>().unwrap();
-        if have_symbol(&range, prev, line, next) {
-            *connected_sum += part_id;
-        }
-        let glist = have_gears(&range, l, prev, line, next);
-        for gear_pos in glist {
-            let g = gears.entry(gear_pos).or_insert_with(|| Gear {
-                nb_parts: 0,
-                product: 1,
-            });
-            g.nb_parts += 1;
-            g.product *= part_id;
-        }
+    println!("symbol | count | part numbers | parts per symbol (parts: symbols)");
+    for (symbol, (count, parts, histogram)) in stats {
+        let histogram = histogram
+            .iter()
+            .map(|(parts, symbols)| format!("{}: {}", parts, symbols))
+            .collect::<Vec<String>>();
+        println!(
+            "{} | {} | {} | {}",
+            symbol as char,
+            count,
+            parts.len(),
+            histogram.join(", ")
+        );
     }
 }
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    let params = config::day_params("day-03", &args.params)?;
 
-    let file = File::open(args.input)?;
-    let mut reader = BufReader::new(file);
+    let rules = match args.rule.is_empty() {
+        true => params.get("gears", vec!["*>=2:product".to_string()])?,
+        false => args.rule.clone(),
+    };
+    let rules = rules
+        .iter()
+        .map(|rule| Rule::parse(rule))
+        .collect::<std::io::Result<Vec<Rule>>>()?;
 
-    let mut result: u32 = 0;
-    let mut gears = HashMap::<(usize, usize), Gear>::new();
-    let re = Regex::new("([0-9]+)").unwrap();
+    let schematic = Schematic::parse(&std::fs::read(args.input)?)?;
 
-    let mut l = 1; // line number
-    let mut line: String = String::new();
-    let _ = reader.read_line(&mut line);
-    let mut prev = ".".repeat(line.len());
-    for next in reader.lines() {
-        let next = next.unwrap();
-        process_line(&re, l, &mut gears, &mut result, &prev, &line, &next);
-
-        prev = line;
-        line = next;
-        l += 1;
+    if args.render {
+        display::print(&schematic, &rules);
+    }
+    if args.stats {
+        print_stats(&schematic);
+    }
+    if let Some(filename) = &args.graph {
+        graph::export(filename, &schematic, &rules)?;
     }
 
-    let next = ".".repeat(line.len());
-    process_line(&re, l, &mut gears, &mut result, &prev, &line, &next);
-
-    let mut product_sum = 0;
-    for ((_, _), g) in gears {
-        if g.nb_parts > 1 {
-            product_sum += g.product;
+    let result: u32 = (0..schematic.numbers.len())
+        .filter(|&n| schematic.is_part(n))
+        .map(|n| schematic.numbers[n].value)
+        .sum();
+
+    // Total of the gears of each rule
+    let mut gear_sums = vec![0; rules.len()];
+    for (s, symbol) in schematic.symbols.iter().enumerate() {
+        let parts = schematic.parts_around(s);
+        for (sum, rule) in gear_sums.iter_mut().zip(&rules) {
+            *sum += rule.apply(symbol.symbol, &parts).unwrap_or(0);
+        }
+    }
+    if rules.len() > 1 {
+        for (sum, rule) in gear_sums.iter().zip(&rules) {
+            println!("Gears {}: {}", rule, sum);
         }
     }
 
     println!("Sum of parts (1st half) {}", result);
-    println!("Product sum  (2nd half) {}", product_sum);
+    println!("Product sum  (2nd half) {}", gear_sums.iter().sum::<u64>());
     Ok(())
 }
@@ -0,0 +1,95 @@
+// Gear rules: which symbols are gears and how their numbers are combined.
+//
+// A rule is written `<symbol><count>:<aggregate>`, e.g.
+//
+//   *>=2:product   a '*' next to at least 2 parts, product of the parts
+//   *=2:product    a '*' next to exactly 2 parts
+//   #=1:sum        a '#' next to a single part
+
+use std::fmt;
+use std::io::{Error, ErrorKind};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Count {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Aggregate {
+    Product,
+    Sum,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    pub symbol: u8,
+    pub count: Count,
+    pub aggregate: Aggregate,
+}
+
+impl Rule {
+    pub fn parse(text: &str) -> std::io::Result<Self> {
+        let invalid = || {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid rule '{}', expected e.g. '*>=2:product'", text),
+            )
+        };
+        let symbol = text
+            .bytes()
+            .next()
+            .filter(u8::is_ascii)
+            .ok_or_else(invalid)?;
+        let (count, aggregate) = text[1..].split_once(':').unwrap_or((&text[1..], "product"));
+        let count = match count.strip_prefix(">=") {
+            Some(n) => Count::AtLeast(n.parse().map_err(|_| invalid())?),
+            None => Count::Exactly(
+                count
+                    .strip_prefix('=')
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(invalid)?,
+            ),
+        };
+        let aggregate = match aggregate {
+            "product" => Aggregate::Product,
+            "sum" => Aggregate::Sum,
+            _ => return Err(invalid()),
+        };
+        Ok(Rule {
+            symbol,
+            count,
+            aggregate,
+        })
+    }
+
+    /// Value of a symbol next to the `parts`, None if it is not a gear
+    pub fn apply(&self, symbol: u8, parts: &[u32]) -> Option<u64> {
+        let count_ok = match self.count {
+            Count::Exactly(n) => parts.len() == n,
+            Count::AtLeast(n) => parts.len() >= n,
+        };
+        if symbol != self.symbol || !count_ok {
+            return None;
+        }
+        let parts = parts.iter().map(|&p| p as u64);
+        Some(match self.aggregate {
+            Aggregate::Product => parts.product(),
+            Aggregate::Sum => parts.sum(),
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let count = match self.count {
+            Count::Exactly(n) => format!("={}", n),
+            Count::AtLeast(n) => format!(">={}", n),
+        };
+        let aggregate = match self.aggregate {
+            Aggregate::Product => "product",
+            Aggregate::Sum => "sum",
+        };
+        write!(f, "{}{}:{}", self.symbol as char, count, aggregate)
+    }
+}
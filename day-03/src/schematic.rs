@@ -0,0 +1,149 @@
+// Parsed engine schematic.
+//
+// The grid is kept as bytes, numbers and symbols are extracted in one
+// pass, then each number looks at the cells around it once to build the
+// adjacency index: the whole parsing is linear in the size of the grid.
+// Short lines are padded with '.', blank lines are empty rows.
+
+use core::ops::Range;
+
+use crate::rules::Rule;
+
+#[derive(Debug)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    pub cols: Range<usize>,
+}
+
+#[derive(Debug)]
+pub struct Symbol {
+    pub symbol: u8,
+    pub row: usize,
+    pub col: usize,
+}
+
+pub struct Schematic {
+    pub width: usize,
+    pub height: usize,
+    pub grid: Vec<u8>,
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    /// symbols around each number, by index
+    pub number_symbols: Vec<Vec<usize>>,
+    /// numbers around each symbol, by index
+    pub symbol_numbers: Vec<Vec<usize>>,
+}
+
+fn is_symbol(c: u8) -> bool {
+    c != b'.' && !c.is_ascii_digit()
+}
+
+impl Schematic {
+    pub fn parse(text: &[u8]) -> std::io::Result<Self> {
+        let text = text.strip_suffix(b"\n").unwrap_or(text);
+        let lines = match text.is_empty() {
+            true => Vec::new(),
+            false => text
+                .split(|&c| c == b'\n')
+                .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+                .collect::<Vec<&[u8]>>(),
+        };
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let height = lines.len();
+        let mut grid = Vec::with_capacity(width * height);
+        for line in &lines {
+            grid.extend_from_slice(line);
+            grid.resize(grid.len() + width - line.len(), b'.');
+        }
+
+        // Numbers and symbols, and the symbol at each position
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+        let mut symbol_at = vec![None; grid.len()];
+        for row in 0..height {
+            let line = &grid[row * width..(row + 1) * width];
+            let mut col = 0;
+            while col < width {
+                let c = line[col];
+                if c.is_ascii_digit() {
+                    let start = col;
+                    let mut value = 0;
+                    while col < width && line[col].is_ascii_digit() {
+                        value = value * 10 + (line[col] - b'0') as u32;
+                        col += 1;
+                    }
+                    numbers.push(Number {
+                        value,
+                        row,
+                        cols: start..col,
+                    });
+                    continue;
+                }
+                if is_symbol(c) {
+                    symbol_at[row * width + col] = Some(symbols.len());
+                    symbols.push(Symbol {
+                        symbol: c,
+                        row,
+                        col,
+                    });
+                }
+                col += 1;
+            }
+        }
+
+        // Adjacency: the box one cell larger than the number
+        let mut number_symbols = vec![Vec::new(); numbers.len()];
+        let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+        for (n, number) in numbers.iter().enumerate() {
+            let rows = number.row.saturating_sub(1)..(number.row + 2).min(height);
+            let cols = number.cols.start.saturating_sub(1)..(number.cols.end + 1).min(width);
+            for row in rows {
+                for col in cols.clone() {
+                    if let Some(s) = symbol_at[row * width + col] {
+                        number_symbols[n].push(s);
+                        symbol_numbers[s].push(n);
+                    }
+                }
+            }
+        }
+
+        Ok(Schematic {
+            width,
+            height,
+            grid,
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        })
+    }
+
+    /// Values of the numbers next to a symbol
+    pub fn parts_around(&self, symbol: usize) -> Vec<u32> {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|&n| self.numbers[n].value)
+            .collect()
+    }
+
+    /// Rules matched by each symbol, with the gear value
+    pub fn gears<'a>(&self, rules: &'a [Rule]) -> Vec<Vec<(&'a Rule, u64)>> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .map(|(s, symbol)| {
+                let parts = self.parts_around(s);
+                rules
+                    .iter()
+                    .filter_map(|rule| Some((rule, rule.apply(symbol.symbol, &parts)?)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// A part number is next to at least one symbol
+    pub fn is_part(&self, number: usize) -> bool {
+        !self.number_symbols[number].is_empty()
+    }
+}
@@ -0,0 +1,57 @@
+// A scratchcard: `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
+
+use std::io::{Error, ErrorKind};
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: usize,
+    pub winning: Vec<u32>,
+    pub mine: Vec<u32>,
+}
+
+fn numbers(text: &str) -> Option<Vec<u32>> {
+    text.split_whitespace()
+        .map(|n| n.parse::<u32>().ok())
+        .collect()
+}
+
+impl Card {
+    pub fn parse(line: &str) -> std::io::Result<Self> {
+        let card = line
+            .strip_prefix("Card")
+            .and_then(|line| line.split_once(':'))
+            .and_then(|(id, lists)| {
+                let (winning, mine) = lists.split_once('|')?;
+                Some(Card {
+                    id: id.trim().parse().ok()?,
+                    winning: numbers(winning)?,
+                    mine: numbers(mine)?,
+                })
+            });
+        card.ok_or(Error::new(
+            ErrorKind::InvalidData,
+            format!("invalid card '{}'", line),
+        ))
+    }
+
+    /// Number of my numbers in the winning list
+    pub fn matches(&self) -> usize {
+        self.mine
+            .iter()
+            .filter(|n| self.winning.contains(n))
+            .count()
+    }
+
+    /// Numbers written twice in the winning list or in my numbers
+    pub fn duplicates(&self) -> Vec<u32> {
+        let mut duplicates = Vec::new();
+        for list in [&self.winning, &self.mine] {
+            for (i, n) in list.iter().enumerate() {
+                if list[..i].contains(n) && !duplicates.contains(n) {
+                    duplicates.push(*n);
+                }
+            }
+        }
+        duplicates
+    }
+}
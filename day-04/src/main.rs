@@ -1,9 +1,14 @@
-use std::collections::VecDeque;
 use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::io::{prelude::*, BufReader, BufWriter, Error, ErrorKind};
 
 use clap::Parser;
-use regex::Regex;
+use serde_json::json;
+
+mod card;
+mod rules;
+
+use card::Card;
+use rules::{Cascade, Direction, Overflow, Scoring};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -11,6 +16,75 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// write the result of each card to a file (.csv or .json)
+    #[arg(short, long)]
+    table: Option<String>,
+
+    /// points given by the matches of a card
+    #[arg(short, long, value_enum, default_value_t = Scoring::Doubling)]
+    scoring: Scoring,
+
+    /// cards on which copies are won
+    #[arg(short, long, value_enum, default_value_t = Direction::Next)]
+    cascade: Direction,
+
+    /// maximum number of cards won by one card
+    #[arg(long)]
+    cap: Option<usize>,
+
+    /// copies won beyond the last card (first card with --cascade previous)
+    #[arg(short, long, value_enum, default_value_t = Overflow::Drop)]
+    overflow: Overflow,
+}
+
+// Result of one card
+struct Row {
+    id: usize,
+    matches: usize,
+    points: u32,
+    // copies won from the previous cards, the original excluded
+    copies: usize,
+}
+
+fn write_table(filename: &str, rows: &[Row]) -> std::io::Result<()> {
+    let json = match filename.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("csv") => false,
+        Some("json") => true,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{}: expected a .csv or .json file", filename),
+            ))
+        }
+    };
+
+    let mut out = BufWriter::new(File::create(filename)?);
+    if json {
+        let rows = rows
+            .iter()
+            .map(|row| {
+                json!({
+                    "id": row.id,
+                    "matches": row.matches,
+                    "points": row.points,
+                    "copies": row.copies,
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::to_writer_pretty(&mut out, &rows)?;
+        writeln!(out)?;
+    } else {
+        writeln!(out, "id,matches,points,copies")?;
+        for row in rows {
+            writeln!(
+                out,
+                "{},{},{},{}",
+                row.id, row.matches, row.points, row.copies
+            )?;
+        }
+    }
+    out.flush()
 }
 
 fn main() -> std::io::Result<()> {
@@ -19,65 +93,55 @@ fn main() -> std::io::Result<()> {
     let file = File::open(args.input)?;
     let reader = BufReader::new(file);
 
-    let re = Regex::new("Card +([0-9]+):([^\\|]+)\\|([^\\|]+)$").unwrap();
-
-    let mut sum = 0;
-    let mut copies: VecDeque<usize> = VecDeque::new();
-    let mut copy_nb = 0;
-
-    for line in reader.lines() {
-        let line = line.unwrap();
-        let cap = re.captures(&line);
-        let cap = match cap {
-            Some(cap) => cap,
-            None => {
-                println!("{:?}", line);
-                panic!();
-            }
-        };
-
-        // Get the winning cards list
-        let winnings = cap
-            .get(2)
-            .unwrap()
-            .as_str()
-            .split_whitespace()
-            .map(str::parse::<u32>)
-            .filter_map(|v| v.ok())
-            .collect::<Vec<u32>>();
-
-        // Count card that are in the winning list
-        let mines = cap
-            .get(3)
-            .unwrap()
-            .as_str()
-            .split_whitespace()
-            .map(str::parse::<u32>)
-            .filter_map(|v| v.ok())
-            .filter(|v| winnings.contains(v))
-            .count();
-
-        // First half : sum (2 ^ count)
-        let value = match mines {
-            0 => 0,
-            _ => 2_u32.pow(mines as u32 - 1),
-        };
-        sum += value;
-
-        // Second Half : count total scratchcards sets
-        let nb = 1 + copies.pop_front().unwrap_or(0);
-        copy_nb += nb;
-        if copies.len() < mines {
-            copies.resize(mines, 0);
+    let cards = reader
+        .lines()
+        .map(|line| Card::parse(&line?))
+        .collect::<std::io::Result<Vec<Card>>>()?;
+
+    // Copies are won on the next cards: ids must follow each other
+    for (i, card) in cards.iter().enumerate() {
+        if i > 0 && card.id != cards[i - 1].id + 1 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("card {} follows card {}", card.id, cards[i - 1].id),
+            ));
+        }
+        let duplicates = card.duplicates();
+        if !duplicates.is_empty() {
+            eprintln!("card {}: duplicate numbers {:?}", card.id, duplicates);
         }
-        copies.range_mut(0..mines).for_each(|n| *n += nb);
-        println!(
-            "{} {} {} ({:?})",
-            cap.get(1).unwrap().as_str(),
-            mines,
-            nb,
-            copies
-        );
+    }
+
+    let matches = cards.iter().map(Card::matches).collect::<Vec<usize>>();
+    let ids = cards.iter().map(|card| card.id).collect::<Vec<usize>>();
+
+    // First half : sum of the points
+    let points = matches
+        .iter()
+        .map(|&m| args.scoring.points(m))
+        .collect::<Vec<u32>>();
+    let sum: u32 = points.iter().sum();
+
+    // Second Half : count total scratchcards sets
+    let cascade = Cascade {
+        direction: args.cascade,
+        cap: args.cap,
+        overflow: args.overflow,
+    };
+    let instances = cascade.instances(&matches, &ids)?;
+    let copy_nb: usize = instances.iter().sum();
+
+    let rows = (0..cards.len())
+        .map(|i| Row {
+            id: ids[i],
+            matches: matches[i],
+            points: points[i],
+            copies: instances[i] - 1,
+        })
+        .collect::<Vec<Row>>();
+
+    if let Some(filename) = &args.table {
+        write_table(filename, &rows)?;
     }
 
     println!("1st half: {:?}", sum);
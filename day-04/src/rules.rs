@@ -0,0 +1,92 @@
+// Scoring and copy rules of the scratchcards.
+//
+// Points only depend on the number of matches of a card. Copies are won
+// on the cards around it: the next ones as in the puzzle, or the
+// previous ones, optionally capped.
+
+use std::io::{Error, ErrorKind};
+
+use clap::ValueEnum;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Scoring {
+    /// 1 point for the first match, doubled by each other one
+    Doubling,
+    /// 1 point per match
+    Linear,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Direction {
+    /// copies of the next cards
+    Next,
+    /// copies of the previous cards
+    Previous,
+}
+
+/// What to do with copies of cards beyond the first or last card
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Overflow {
+    /// these copies are lost
+    Drop,
+    /// stop with an error
+    Error,
+}
+
+pub struct Cascade {
+    pub direction: Direction,
+    /// maximum number of cards won by a card
+    pub cap: Option<usize>,
+    pub overflow: Overflow,
+}
+
+impl Scoring {
+    pub fn points(&self, matches: usize) -> u32 {
+        match (self, matches) {
+            (_, 0) => 0,
+            (Scoring::Doubling, _) => 2_u32.pow(matches as u32 - 1),
+            (Scoring::Linear, _) => matches as u32,
+        }
+    }
+}
+
+impl Cascade {
+    /// Number of instances of each card, from the matches of all the cards
+    pub fn instances(&self, matches: &[usize], ids: &[usize]) -> std::io::Result<Vec<usize>> {
+        let nb = matches.len();
+        let mut instances = vec![1; nb];
+
+        // Cards are processed in the direction of the copies, so that
+        // the copies of a card are all known before it is played
+        let order: Box<dyn Iterator<Item = usize>> = match self.direction {
+            Direction::Next => Box::new(0..nb),
+            Direction::Previous => Box::new((0..nb).rev()),
+        };
+        for i in order {
+            let won = self.cap.map_or(matches[i], |cap| matches[i].min(cap));
+            let available = match self.direction {
+                Direction::Next => nb - 1 - i,
+                Direction::Previous => i,
+            };
+            if won > available {
+                if let Overflow::Error = self.overflow {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "card {} wins {} cards, only {} are left",
+                            ids[i], won, available
+                        ),
+                    ));
+                }
+            }
+            let targets = match self.direction {
+                Direction::Next => i + 1..i + 1 + won.min(available),
+                Direction::Previous => i - won.min(available)..i,
+            };
+            for target in targets {
+                instances[target] += instances[i];
+            }
+        }
+        Ok(instances)
+    }
+}
@@ -0,0 +1,254 @@
+// Almanac: the seeds, and the maps between two categories.
+//
+//   seed-to-soil map:
+//   50 98 2
+//   52 50 48
+//
+// Maps can be chained in any order in the file: the path between two
+// categories is searched in the graph of the categories.
+
+use std::collections::VecDeque;
+use std::io::{Error, ErrorKind};
+
+use regex::Regex;
+
+use crate::piecewise::Piecewise;
+
+/// Half-open range of values, and whether a rule of the current map
+/// already moved it
+pub type Range = (i64, i64, bool);
+
+/// `len` values starting at `src` go to `dst`
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    pub dst: i64,
+    pub src: i64,
+    pub len: i64,
+}
+
+#[derive(Debug)]
+pub struct Map {
+    pub from: String,
+    pub to: String,
+    pub rules: Vec<Rule>,
+}
+
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub maps: Vec<Map>,
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+// Move the part of the ranges inside the source of the rule,
+// the ranges already moved by another rule are kept as is
+fn apply_rule(ranges: &[Range], rule: &Rule) -> Vec<Range> {
+    let src_start = rule.src;
+    let src_end = rule.src + rule.len;
+    let ofs = rule.dst - rule.src;
+
+    ranges
+        .iter()
+        .flat_map(|(start, end, processed)| {
+            /*
+                        [---- src ----[
+               [---[  [---[  [---[  [---[  [---[
+                 A      B      C      D      E
+                     [--------------------[
+                               F
+            */
+            if (*start >= src_end) || (*end <= src_start) || *processed {
+                //   Case E        ||      Case A
+                vec![(*start, *end, *processed)].into_iter()
+            } else if *start >= src_start {
+                if *end <= src_end {
+                    // Case C, the whole range is envolved
+                    vec![(*start + ofs, *end + ofs, true)].into_iter()
+                } else {
+                    // Case D, split on the end
+                    vec![(*start + ofs, src_end + ofs, true), (src_end, *end, false)].into_iter()
+                }
+            } else if *end <= src_end {
+                // Case B, split on the start
+                vec![
+                    (*start, src_start, false),
+                    (src_start + ofs, *end + ofs, true),
+                ]
+                .into_iter()
+            } else {
+                // Case F, 3 split (on the start & on the end)
+                vec![
+                    (*start, src_start, false),
+                    (src_start + ofs, src_end + ofs, true),
+                    (src_end, *end, false),
+                ]
+                .into_iter()
+            }
+        })
+        .collect::<Vec<Range>>()
+}
+
+// Same ranges, not moved yet
+fn reset(ranges: &[Range]) -> Vec<Range> {
+    ranges
+        .iter()
+        .map(|(start, end, _)| (*start, *end, false))
+        .collect()
+}
+
+// Parts of the ranges not moved by any of the rules
+fn unmoved(rules: &[Rule], ranges: &[Range]) -> Vec<Range> {
+    rules
+        .iter()
+        .fold(reset(ranges), |ranges, rule| apply_rule(&ranges, rule))
+        .into_iter()
+        .filter(|(_, _, processed)| !processed)
+        .collect()
+}
+
+impl Map {
+    /// Image of the ranges, values outside of all the rules are unchanged
+    pub fn apply(&self, ranges: &[Range]) -> Vec<Range> {
+        self.rules
+            .iter()
+            .fold(reset(ranges), |ranges, rule| apply_rule(&ranges, rule))
+    }
+
+    /// Values whose image is in the ranges: the values left unchanged,
+    /// and for each rule the values it moves into the ranges
+    pub fn preimage(&self, ranges: &[Range]) -> Vec<Range> {
+        let mut preimage = unmoved(&self.rules, ranges);
+        for (i, rule) in self.rules.iter().enumerate() {
+            let inverse = Rule {
+                dst: rule.src,
+                src: rule.dst,
+                len: rule.len,
+            };
+            let moved = apply_rule(&reset(ranges), &inverse)
+                .into_iter()
+                .filter(|(_, _, processed)| *processed)
+                .collect::<Vec<Range>>();
+            // values taken by a previous rule do not go through this one
+            preimage.extend(unmoved(&self.rules[..i], &moved));
+        }
+        preimage
+    }
+}
+
+impl Almanac {
+    pub fn parse(text: &str) -> std::io::Result<Self> {
+        let re_seeds = Regex::new("seeds: ([ 0-9]+)").unwrap();
+        let re_head = Regex::new("(.+)-to-(.+) map:").unwrap();
+        let re_range = Regex::new("([0-9]+) ([0-9]+) ([0-9]+)").unwrap();
+
+        let mut lines = text.lines();
+        let seeds = lines
+            .next()
+            .and_then(|line| re_seeds.captures(line))
+            .ok_or(invalid_data(
+                "expected seeds: on the first line".to_string(),
+            ))?
+            .get(1)
+            .unwrap()
+            .as_str()
+            .split_whitespace()
+            .map(str::parse::<i64>)
+            .filter_map(|v| v.ok())
+            .collect::<Vec<i64>>();
+
+        let mut maps = Vec::<Map>::new();
+        for line in lines {
+            if let Some(cap) = re_head.captures(line) {
+                // begin a new translation ruleset
+                maps.push(Map {
+                    from: cap.get(1).unwrap().as_str().to_string(),
+                    to: cap.get(2).unwrap().as_str().to_string(),
+                    rules: Vec::new(),
+                });
+            } else if let Some(cap) = re_range.captures(line) {
+                let value = |i| cap.get(i).unwrap().as_str().parse::<i64>().unwrap();
+                let map = maps
+                    .last_mut()
+                    .ok_or(invalid_data(format!("'{}' is not in a map", line)))?;
+                map.rules.push(Rule {
+                    dst: value(1),
+                    src: value(2),
+                    len: value(3),
+                });
+            }
+        }
+
+        Ok(Almanac { seeds, maps })
+    }
+
+    /// Maps to apply in order to go from one category to another
+    pub fn path(&self, from: &str, to: &str) -> std::io::Result<Vec<&Map>> {
+        // Breadth first search, remembering the map reaching each category
+        let mut reached_by = vec![None; self.maps.len()];
+        let mut todo = VecDeque::from([(from, None)]);
+        let mut seen = vec![from];
+        while let Some((category, by)) = todo.pop_front() {
+            if category == to {
+                // walk back from the last map
+                let mut path = Vec::new();
+                let mut current = by;
+                while let Some(m) = current {
+                    path.push(&self.maps[m]);
+                    current = reached_by[m];
+                }
+                path.reverse();
+                return Ok(path);
+            }
+            for (m, map) in self.maps.iter().enumerate() {
+                if map.from == category && !seen.contains(&map.to.as_str()) {
+                    seen.push(&map.to);
+                    reached_by[m] = by;
+                    todo.push_back((&map.to, Some(m)));
+                }
+            }
+        }
+        Err(invalid_data(format!(
+            "no chain of maps from {} to {}",
+            from, to
+        )))
+    }
+
+    /// Image of the ranges of category `from` in category `to`
+    pub fn map_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: &[Range],
+    ) -> std::io::Result<Vec<Range>> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(ranges.to_vec(), |ranges, map| map.apply(&ranges)))
+    }
+
+    /// Values of category `from` whose image in category `to` is in the ranges
+    pub fn preimage_ranges(
+        &self,
+        from: &str,
+        to: &str,
+        ranges: &[Range],
+    ) -> std::io::Result<Vec<Range>> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .rev()
+            .fold(ranges.to_vec(), |ranges, map| map.preimage(&ranges)))
+    }
+
+    /// Single function going from category `from` to category `to`
+    pub fn compose(&self, from: &str, to: &str) -> std::io::Result<Piecewise> {
+        Ok(self
+            .path(from, to)?
+            .iter()
+            .fold(Piecewise::identity(), |f, map| {
+                f.then(&Piecewise::from_map(map))
+            }))
+    }
+}
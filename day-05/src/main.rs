@@ -1,9 +1,12 @@
-use std::fs::File;
-use std::io::{prelude::*, BufReader};
-
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use itertools::Itertools;
-use regex::Regex;
+
+mod almanac;
+mod piecewise;
+mod validate;
+
+use almanac::{Almanac, Range};
+use validate::Overlaps;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -11,97 +14,200 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// category of the queried values
+    #[arg(short, long, default_value = "seed")]
+    from: String,
+
+    /// category to map the queried values to
+    #[arg(short, long, default_value = "location")]
+    to: String,
+
+    /// value ("79") or range ("79+14") to map (can be repeated)
+    #[arg(short, long)]
+    query: Vec<String>,
+
+    /// the queries are values of the `to` category, mapped back to `from`
+    #[arg(short, long)]
+    reverse: bool,
+
+    /// print the function composed of all the maps from `from` to `to`
+    #[arg(long)]
+    table: bool,
+
+    /// report overlapping, empty and identity rules of the maps
+    #[arg(long)]
+    validate: bool,
+
+    /// values in the sources of several rules of a map
+    #[arg(long, value_enum, default_value_t = Overlaps::FirstMatch)]
+    overlaps: Overlaps,
+
+    /// interpretation of the seeds line (default: both answers)
+    #[arg(short, long, value_enum)]
+    seeds_as: Option<SeedsAs>,
 }
 
-// Returns a list of range ( vec of tuple (start, end, processed) )
-fn init_seeds(line: &str) -> Vec<(i64, i64, bool)> {
-    let re_seeds = Regex::new("seeds: ([ 0-9]+)").unwrap();
-
-    re_seeds
-        .captures(line)
-        .unwrap()
-        .get(1)
-        .unwrap()
-        .as_str()
-        .split_whitespace()
-        .map(str::parse::<i64>)
-        .filter_map(|v| v.ok())
-        .tuples()
-        .map(|(start, len)| (start, start + len, false))
-        .collect::<Vec<(i64, i64, bool)>>()
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SeedsAs {
+    /// each number is a seed (part 1)
+    Values,
+    /// pairs of (start, len) (part 2)
+    Ranges,
+}
+
+impl SeedsAs {
+    fn name(&self) -> &'static str {
+        match self {
+            SeedsAs::Values => "values",
+            SeedsAs::Ranges => "ranges",
+        }
+    }
+
+    // Seeds as ranges, a value is a range of length 1
+    fn seeds(&self, almanac: &Almanac) -> std::io::Result<Vec<Range>> {
+        let invalid = |message: &str| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string())
+        };
+        if almanac.seeds.is_empty() {
+            return Err(invalid("no seeds"));
+        }
+        match self {
+            SeedsAs::Values => Ok(almanac
+                .seeds
+                .iter()
+                .map(|seed| (*seed, seed + 1, false))
+                .collect()),
+            SeedsAs::Ranges if !almanac.seeds.len().is_multiple_of(2) => Err(invalid(
+                "seeds as ranges: expected pairs of (start, len), got an odd count",
+            )),
+            SeedsAs::Ranges => Ok(almanac
+                .seeds
+                .iter()
+                .tuples()
+                .map(|(start, len)| (*start, start + len, false))
+                .collect()),
+        }
+    }
+}
+
+// "79" is a single value, "79+14" the 14 values from 79
+fn parse_query(query: &str) -> std::io::Result<Range> {
+    let (start, len) = query.split_once('+').unwrap_or((query, "1"));
+    match (start.trim().parse::<i64>(), len.trim().parse::<i64>()) {
+        (Ok(start), Ok(len)) => Ok((start, start + len, false)),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "invalid query '{}', expected <start> or <start>+<len>",
+                query
+            ),
+        )),
+    }
+}
+
+fn format_ranges(ranges: &[Range]) -> String {
+    ranges
+        .iter()
+        .sorted()
+        .map(|(start, end, _)| format!("{}..{}", start, end))
+        .join(", ")
+}
+
+// Parts of the ranges `a` inside the ranges `b`
+fn intersect(a: &[Range], b: &[Range]) -> Vec<Range> {
+    a.iter()
+        .cartesian_product(b)
+        .map(|(a, b)| (a.0.max(b.0), a.1.min(b.1), false))
+        .filter(|(start, end, _)| start < end)
+        .collect()
 }
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
 
-    let file = File::open(args.input)?;
-    let mut lines = BufReader::new(file).lines();
+    let almanac = Almanac::parse(&std::fs::read_to_string(args.input)?)?;
 
-    let re_head = Regex::new("(.+)-to-(.+) map:").unwrap();
-    let re_range = Regex::new("([0-9]+) ([0-9]+) ([0-9]+)").unwrap();
+    // The whole chain must be there, whatever the order of the maps
+    almanac.path("seed", "location")?;
 
-    let mut input = init_seeds(lines.next().unwrap().unwrap().as_ref());
+    let reports = validate::validate(&almanac);
+    if args.validate {
+        for report in &reports {
+            println!("{}", report);
+        }
+    }
+    if let (Overlaps::Error, Some(report)) =
+        (args.overlaps, reports.iter().find(|r| r.is_overlap()))
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            report.to_string(),
+        ));
+    }
 
-    for line in lines {
-        let line = line.unwrap();
+    let interpretations = match args.seeds_as {
+        Some(seeds_as) => vec![seeds_as],
+        None => vec![SeedsAs::Values, SeedsAs::Ranges],
+    };
 
-        if let Some(cap) = re_head.captures(&line) {
-            // begin a new translation ruleset
-            println!("{} status {:?}", cap.get(1).unwrap().as_str(), input);
-            input.iter_mut().for_each(|t| t.2 = false);
-        } else if let Some(cap) = re_range.captures(&line) {
-            let dst_start = cap.get(1).unwrap().as_str().parse::<i64>().unwrap();
-            let src_start = cap.get(2).unwrap().as_str().parse::<i64>().unwrap();
-            let len = cap.get(3).unwrap().as_str().parse::<i64>().unwrap();
-            let src_end = src_start + len;
-            let ofs: i64 = dst_start - src_start;
+    for query in &args.query {
+        let range = parse_query(query)?;
+        if args.reverse {
+            let preimage = almanac.preimage_ranges(&args.from, &args.to, &[range])?;
+            println!(
+                "{} {} <- {} {}",
+                args.to,
+                format_ranges(&[range]),
+                args.from,
+                format_ranges(&preimage)
+            );
+            if args.from == "seed" {
+                for seeds_as in &interpretations {
+                    println!(
+                        "  in the seeds ({}): {}",
+                        seeds_as.name(),
+                        format_ranges(&intersect(&preimage, &seeds_as.seeds(&almanac)?))
+                    );
+                }
+            }
+        } else {
+            let image = almanac.map_ranges(&args.from, &args.to, &[range])?;
+            println!(
+                "{} {} -> {} {}",
+                args.from,
+                format_ranges(&[range]),
+                args.to,
+                format_ranges(&image)
+            );
+        }
+    }
 
-            input = input
-                .iter()
-                .flat_map(|(start, end, processed)| {
-                    /*
-                                [---- src ----[
-                       [---[  [---[  [---[  [---[  [---[
-                         A      B      C      D      E
-                             [--------------------[
-                                       F
-                    */
-                    if (*start >= src_end) || (*end <= src_start) || *processed {
-                        //   Case E        ||      Case A
-                        vec![(*start, *end, *processed)].into_iter()
-                    } else if *start >= src_start {
-                        if *end <= src_end {
-                            // Case C, the whole range is envolved
-                            vec![(*start + ofs, *end + ofs, true)].into_iter()
-                        } else {
-                            // Case D, split on the end
-                            vec![(*start + ofs, src_end + ofs, true), (src_end, *end, false)]
-                                .into_iter()
-                        }
-                    } else if *end <= src_end {
-                        // Case B, split on the start
-                        vec![
-                            (*start, src_start, false),
-                            (src_start + ofs, *end + ofs, true),
-                        ]
-                        .into_iter()
-                    } else {
-                        // Case F, 3 split (on the start & on the end)
-                        vec![
-                            (*start, src_start, false),
-                            (src_start + ofs, src_end + ofs, true),
-                            (src_end, *end, false),
-                        ]
-                        .into_iter()
-                    }
-                })
-                .collect::<Vec<(i64, i64, bool)>>();
+    if args.table {
+        let f = almanac.compose(&args.from, &args.to)?;
+        println!(
+            "{:>20} {:>20} {:>12}",
+            format!("{} start", args.from),
+            "end",
+            "offset"
+        );
+        for piece in &f.pieces {
+            println!("{:>20} {:>20} {:>+12}", piece.start, piece.end, piece.ofs);
         }
     }
 
-    println!("locations {:?}", input);
+    // All the maps at once
+    let seed_to_location = almanac.compose("seed", "location")?;
 
-    println!("lowest location: {:?}", input.iter().min().unwrap());
+    for seeds_as in &interpretations {
+        let lowest = seed_to_location
+            .min_image(&seeds_as.seeds(&almanac)?)
+            .ok_or(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("seeds as {}: all the ranges are empty", seeds_as.name()),
+            ))?;
+        println!("lowest location (seeds as {}): {}", seeds_as.name(), lowest);
+    }
 
     Ok(())
 }
@@ -0,0 +1,121 @@
+// Piecewise translation: the values of each piece are moved by the same
+// offset.
+//
+// Pieces are sorted, contiguous and cover the whole domain, two
+// neighbours never have the same offset: a function has a single
+// representation, whatever the maps it was built from.
+
+use crate::almanac::{Map, Range};
+
+// Values handled by the functions, the others are left unchanged
+const DOMAIN: (i64, i64) = (0, 1 << 62);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Piece {
+    pub start: i64,
+    pub end: i64,
+    pub ofs: i64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Piecewise {
+    pub pieces: Vec<Piece>,
+}
+
+impl Piecewise {
+    pub fn identity() -> Self {
+        Piecewise {
+            pieces: vec![Piece {
+                start: DOMAIN.0,
+                end: DOMAIN.1,
+                ofs: 0,
+            }],
+        }
+    }
+
+    /// Function of a map: the first rule containing a value moves it
+    pub fn from_map(map: &Map) -> Self {
+        let mut bounds = vec![DOMAIN.0, DOMAIN.1];
+        for rule in &map.rules {
+            bounds.push(rule.src.clamp(DOMAIN.0, DOMAIN.1));
+            bounds.push((rule.src + rule.len).clamp(DOMAIN.0, DOMAIN.1));
+        }
+        bounds.sort();
+        bounds.dedup();
+
+        let pieces = bounds
+            .windows(2)
+            .map(|w| Piece {
+                start: w[0],
+                end: w[1],
+                ofs: map
+                    .rules
+                    .iter()
+                    .find(|rule| rule.src <= w[0] && w[0] < rule.src + rule.len)
+                    .map_or(0, |rule| rule.dst - rule.src),
+            })
+            .collect();
+        Piecewise::normalized(pieces)
+    }
+
+    // Sort the pieces and merge the neighbours with the same offset
+    fn normalized(mut pieces: Vec<Piece>) -> Self {
+        pieces.sort_by_key(|piece| piece.start);
+        let mut merged: Vec<Piece> = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            match merged.last_mut() {
+                Some(last) if last.end == piece.start && last.ofs == piece.ofs => {
+                    last.end = piece.end
+                }
+                _ => merged.push(piece),
+            }
+        }
+        Piecewise { pieces: merged }
+    }
+
+    // Index of the piece containing a value, if in the domain
+    fn find(&self, value: i64) -> Option<usize> {
+        let i = self.pieces.partition_point(|piece| piece.end <= value);
+        (i < self.pieces.len() && self.pieces[i].start <= value).then_some(i)
+    }
+
+    /// `self` then `next`
+    pub fn then(&self, next: &Piecewise) -> Piecewise {
+        let mut pieces = Vec::new();
+        for piece in &self.pieces {
+            // the image of the piece crosses some pieces of `next`
+            let (start, end) = (piece.start + piece.ofs, piece.end + piece.ofs);
+            let mut i = next.find(start).unwrap_or(0);
+            while i < next.pieces.len() && next.pieces[i].start < end {
+                let other = &next.pieces[i];
+                let (s, e) = (start.max(other.start), end.min(other.end));
+                if s < e {
+                    pieces.push(Piece {
+                        start: s - piece.ofs,
+                        end: e - piece.ofs,
+                        ofs: piece.ofs + other.ofs,
+                    });
+                }
+                i += 1;
+            }
+        }
+        Piecewise::normalized(pieces)
+    }
+
+    /// Lowest image of the values of the ranges
+    pub fn min_image(&self, ranges: &[Range]) -> Option<i64> {
+        ranges
+            .iter()
+            .filter(|(start, end, _)| start < end)
+            .filter_map(|&(start, end, _)| {
+                // lowest value of each piece crossed by the range
+                let first = self.find(start)?;
+                self.pieces[first..]
+                    .iter()
+                    .take_while(|piece| piece.start < end)
+                    .map(|piece| start.max(piece.start) + piece.ofs)
+                    .min()
+            })
+            .min()
+    }
+}
@@ -0,0 +1,128 @@
+// Checks of the rules of each map.
+//
+// When the sources of two rules overlap, the first rule of the map moves
+// the common values: the result depends on the order of the lines.
+// Empty rules, rules moving values to themselves and the gaps between the
+// sources (values no rule moves) change nothing, they are only reported.
+
+use std::fmt;
+
+use clap::ValueEnum;
+
+use crate::almanac::{Almanac, Map, Rule};
+
+/// How values in the sources of several rules of a map are handled
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Overlaps {
+    /// the first rule of the map moves them
+    FirstMatch,
+    /// the almanac is rejected
+    Error,
+}
+
+pub enum Issue {
+    /// rules (index in the map) and the common source values
+    Overlap(usize, usize, i64, i64),
+    Empty(usize),
+    Identity(usize),
+    /// values in no source, left unchanged (end is i64::MAX above all of them)
+    Gap(i64, i64),
+}
+
+pub struct Report<'a> {
+    pub map: &'a Map,
+    pub issue: Issue,
+}
+
+fn describe(map: &Map, i: usize) -> String {
+    let Rule { dst, src, len } = map.rules[i];
+    format!("rule {} ({} {} {})", i + 1, dst, src, len)
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let map = self.map;
+        write!(f, "{}-to-{}: ", map.from, map.to)?;
+        match self.issue {
+            Issue::Overlap(i, j, start, end) => write!(
+                f,
+                "{} and {} both move {}..{}",
+                describe(map, i),
+                describe(map, j),
+                start,
+                end
+            ),
+            Issue::Empty(i) => write!(f, "{} is empty", describe(map, i)),
+            Issue::Identity(i) => write!(f, "{} does not move anything", describe(map, i)),
+            Issue::Gap(start, i64::MAX) => write!(f, "values from {} are unchanged", start),
+            Issue::Gap(start, end) => write!(f, "values {}..{} are unchanged", start, end),
+        }
+    }
+}
+
+impl Report<'_> {
+    pub fn is_overlap(&self) -> bool {
+        matches!(self.issue, Issue::Overlap(..))
+    }
+}
+
+// Values (from 0) in the source of no rule of the map
+fn gaps(map: &Map) -> Vec<(i64, i64)> {
+    let mut sources = map
+        .rules
+        .iter()
+        .filter(|rule| rule.len > 0)
+        .map(|rule| (rule.src, rule.src + rule.len))
+        .collect::<Vec<(i64, i64)>>();
+    sources.sort();
+
+    let mut gaps = Vec::new();
+    let mut covered = 0;
+    for (start, end) in sources {
+        if start > covered {
+            gaps.push((covered, start));
+        }
+        covered = covered.max(end);
+    }
+    gaps.push((covered, i64::MAX));
+    gaps
+}
+
+/// All the issues of the maps of the almanac
+pub fn validate(almanac: &Almanac) -> Vec<Report<'_>> {
+    let mut reports = Vec::new();
+    for map in &almanac.maps {
+        for (i, rule) in map.rules.iter().enumerate() {
+            if rule.len <= 0 {
+                reports.push(Report {
+                    map,
+                    issue: Issue::Empty(i),
+                });
+                continue;
+            }
+            if rule.dst == rule.src {
+                reports.push(Report {
+                    map,
+                    issue: Issue::Identity(i),
+                });
+            }
+            for (j, other) in map.rules.iter().enumerate().skip(i + 1) {
+                let start = rule.src.max(other.src);
+                let end = (rule.src + rule.len).min(other.src + other.len);
+                if start < end {
+                    reports.push(Report {
+                        map,
+                        issue: Issue::Overlap(i, j, start, end),
+                    });
+                }
+            }
+        }
+        for (start, end) in gaps(map) {
+            reports.push(Report {
+                map,
+                issue: Issue::Gap(start, end),
+            });
+        }
+    }
+    reports
+}
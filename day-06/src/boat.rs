@@ -0,0 +1,161 @@
+// Boat physics.
+//
+// Each millisecond spent holding the button adds `acceleration` to the
+// speed, up to `max_speed`. Once released, the boat moves at that speed,
+// and with drag it loses a fraction `drag` (0 to 1) of its speed every
+// millisecond:
+//
+//   distance = speed * (1 + (1 - drag) + (1 - drag)^2 + ...)
+//
+// The distance grows then shrinks with the hold time: the winning hold
+// times are a single window.
+
+pub struct Boat {
+    pub acceleration: u64,
+    pub max_speed: Option<u64>,
+    pub drag: f64,
+}
+
+impl Boat {
+    /// Speed once the button is released
+    pub fn speed(&self, hold: u64) -> u128 {
+        let speed = hold as u128 * self.acceleration as u128;
+        self.max_speed.map_or(speed, |max| speed.min(max as u128))
+    }
+
+    /// Distance reached in a race of `time` milliseconds
+    pub fn distance(&self, time: u64, hold: u64) -> f64 {
+        let moving = (time - hold) as f64;
+        let speed = self.speed(hold) as f64;
+        if self.drag == 0.0 {
+            speed * moving
+        } else {
+            speed * (1.0 - (1.0 - self.drag).powf(moving)) / self.drag
+        }
+    }
+
+    /// First and last hold times beating the record, None if none does
+    pub fn winning_range(&self, time: u64, record: u64) -> Option<(u64, u64)> {
+        if self.drag == 0.0 {
+            self.exact_range(time, record)
+        } else {
+            self.search_range(time, record)
+        }
+    }
+
+    // Without drag, distance = speed * (time - hold) is exact:
+    // - while accelerating, acceleration * hold * (time - hold) > record
+    //   is the original race with record / acceleration
+    // - at max speed, max_speed * (time - hold) > record is linear
+    fn exact_range(&self, time: u64, record: u64) -> Option<(u64, u64)> {
+        if self.acceleration == 0 {
+            return None;
+        }
+        // integer distances: a * x > record <=> x > record / a
+        let accelerating = winning_range(time, record / self.acceleration);
+        let max_speed = match self.max_speed {
+            None => return accelerating,
+            Some(max_speed) => max_speed,
+        };
+
+        // first hold time reaching the max speed
+        let full_speed = max_speed.div_ceil(self.acceleration);
+        let ramp = accelerating
+            .filter(|(inf, _)| *inf < full_speed)
+            .map(|(inf, sup)| (inf, sup.min(full_speed - 1)));
+        // at max speed: hold < time - record / max_speed
+        let cruise = (max_speed > 0)
+            .then(|| time.checked_sub(record / max_speed + 1))
+            .flatten()
+            .filter(|sup| full_speed <= *sup)
+            .map(|sup| (full_speed, sup));
+
+        match (ramp, cruise) {
+            (Some((inf, sup)), Some((_, cruise_sup))) => Some((inf, sup.max(cruise_sup))),
+            (ramp, cruise) => ramp.or(cruise),
+        }
+    }
+
+    // With drag, binary searches on each side of the best hold time
+    fn search_range(&self, time: u64, record: u64) -> Option<(u64, u64)> {
+        let beats = |hold: u64| self.distance(time, hold) > record as f64;
+
+        // best hold time: first one not improved by holding longer
+        let (mut lo, mut hi) = (0, time);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.distance(time, mid) < self.distance(time, mid + 1) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        let best = lo;
+        if !beats(best) {
+            return None;
+        }
+
+        // first winning hold time, distance is increasing up to best
+        let (mut lo, mut hi) = (0, best);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if beats(mid) {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        let crit_inf = lo;
+
+        // last winning hold time, distance is decreasing after best
+        let (mut lo, mut hi) = (best, time);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+            if beats(mid) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        Some((crit_inf, lo))
+    }
+}
+
+/// First and last hold times beating the record in the original race,
+/// where the speed is the hold time. None if none does.
+pub fn winning_range(time: u64, record: u64) -> Option<(u64, u64)> {
+    /*
+        searching for distance > record.
+        distance = (hold_time) * (time - hold_time)
+
+        so: record < hold_time * (time - hold_time)
+            record < time*hold_time - hold_time^2
+
+        Search the critical point:
+            0 = hold_time^2 -time*hold_time + record
+            hold_time = (time +/- sqrt(time^2 - 4*record)) / 2
+
+        Computed on u128 with an integer square root: time^2 does not
+        overflow and there is no rounding.
+    */
+    let (time, record) = (time as u128, record as u128);
+    let beats = |hold: u128| hold * (time - hold) > record;
+
+    // No real root: the record can't be reached
+    let delta = (time * time).checked_sub(4 * record)?;
+
+    // isqrt(delta) <= sqrt(delta) < isqrt(delta) + 1, so the first winning
+    // hold time is this one or the next one (when the root is an integer)
+    let mut crit_inf = (time - delta.isqrt()) / 2;
+    if !beats(crit_inf) {
+        crit_inf += 1;
+    }
+    if crit_inf > time || !beats(crit_inf) {
+        return None;
+    }
+    // distance is symmetric around time / 2
+    let crit_sup = time - crit_inf;
+
+    Some((crit_inf as u64, crit_sup as u64))
+}
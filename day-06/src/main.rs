@@ -1,45 +1,79 @@
 use std::fs::File;
-use std::io::{prelude::*, BufReader};
+use std::io::{prelude::*, BufReader, Error, ErrorKind};
 
+use aoc_common::config;
 use clap::Parser;
 
+mod boat;
+
+use boat::Boat;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
-}
 
-fn winning_range(time: u64, record: u64) -> (u64, u64) {
-    /*
-        searching for distance > record.
-        distance = (hold_time) * (time - hold_time)
+    /// print the distance of each hold time, for the races up to this time
+    #[arg(short, long, value_name = "MAX_TIME")]
+    table: Option<u64>,
 
-        so: record < hold_time * (time - hold_time)
-            record < time*hold_time - hold_time^2
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
+}
 
-        Search the critical point:
-            0 = hold_time^2 -time*hold_time + record
-            hold_time = (time +/- sqrt(time^2 - 4*record)) / 2
-    */
-    let crit_inf: f64 = (time as f64 - (time.pow(2) as f64 - 4.0 * record as f64).sqrt()) / 2.0;
-    let crit_sup: f64 = (time as f64 + (time.pow(2) as f64 - 4.0 * record as f64).sqrt()) / 2.0;
+// Single race of the 2nd half: all the digits of the line, spaces ignored
+fn kerned_number(line: &str) -> std::io::Result<u64> {
+    let digits = line
+        .chars()
+        .filter(|c| c.is_ascii_digit())
+        .collect::<String>();
+    digits.parse::<u64>().map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("2nd half race {:?}: {}", digits, e),
+        )
+    })
+}
 
-    let crit_inf = match crit_inf {
-        v if v.ceil() == v => v.ceil() as u64 + 1,
-        v => v.ceil() as u64,
-    };
-    let crit_sup = match crit_sup {
-        v if v.trunc() == v => v.trunc() as u64 - 1,
-        v => v.trunc() as u64,
-    };
+// Number of winning hold times
+fn ways_to_win(boat: &Boat, time: u64, record: u64) -> u64 {
+    boat.winning_range(time, record)
+        .map_or(0, |(crit_inf, crit_sup)| 1 + crit_sup - crit_inf)
+}
 
-    (crit_inf, crit_sup)
+fn print_table(boat: &Boat, time: u64, record: u64) {
+    println!("race of {} ms, record {}", time, record);
+    println!("{:>6} {:>8} {:>12}", "hold", "speed", "distance");
+    for hold in 0..=time {
+        let distance = boat.distance(time, hold);
+        println!(
+            "{:>6} {:>8} {:>12.2}{}",
+            hold,
+            boat.speed(hold),
+            distance,
+            if distance > record as f64 { " *" } else { "" }
+        );
+    }
 }
 
 fn main() -> std::io::Result<()> {
     let args: Args = Args::parse();
+    let params = config::day_params("day-06", &args.params)?;
+
+    let boat = Boat {
+        acceleration: params.get("acceleration", 1)?,
+        max_speed: params.get("max_speed", None)?,
+        drag: params.get("drag", 0.0)?,
+    };
+    if !(0.0..=1.0).contains(&boat.drag) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("drag {}: expected a fraction between 0 and 1", boat.drag),
+        ));
+    }
 
     let file = File::open(args.input)?;
     let mut lines = BufReader::new(file).lines();
@@ -70,34 +104,177 @@ fn main() -> std::io::Result<()> {
             .collect::<String>()
     );
     // 2nd half of the day
-    let only_one_race = (
-        line_times
-            .chars()
-            .filter(|c| c.is_ascii_digit())
-            .collect::<String>()
-            .parse::<u64>()
-            .unwrap(),
-        line_distances
-            .chars()
-            .filter(|c| c.is_ascii_digit())
-            .collect::<String>()
-            .parse::<u64>()
-            .unwrap(),
-    );
+    let only_one_race = (kerned_number(&line_times)?, kerned_number(&line_distances)?);
+
+    if let Some(max_time) = args.table {
+        for (time, record) in races.iter().filter(|(time, _)| *time <= max_time) {
+            print_table(&boat, *time, *record);
+        }
+    }
 
     let mut result = 1;
     for (time, record) in races {
-        let (crit_inf, crit_sup) = winning_range(time, record);
-        println!("{}  {}", crit_inf, crit_sup);
-        result *= 1 + crit_sup - crit_inf;
+        if let Some((crit_inf, crit_sup)) = boat.winning_range(time, record) {
+            println!("{}  {}", crit_inf, crit_sup);
+        }
+        result *= ways_to_win(&boat, time, record);
     }
 
     println!("result for 1st star is {}", result);
 
-    let (crit_inf, crit_sup) = winning_range(only_one_race.0, only_one_race.1);
-    println!("{}  {}", crit_inf, crit_sup);
+    if let Some((crit_inf, crit_sup)) = boat.winning_range(only_one_race.0, only_one_race.1) {
+        println!("{}  {}", crit_inf, crit_sup);
+    }
 
-    println!("result for 2st star is {}", 1 + crit_sup - crit_inf);
+    println!(
+        "result for 2st star is {}",
+        ways_to_win(&boat, only_one_race.0, only_one_race.1)
+    );
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::boat::winning_range;
+
+    // speed = hold time, as in the puzzle
+    const PLAIN: Boat = Boat {
+        acceleration: 1,
+        max_speed: None,
+        drag: 0.0,
+    };
+
+    fn brute_force(time: u64, record: u64) -> u64 {
+        (0..=time)
+            .filter(|hold| (*hold as u128) * ((time - hold) as u128) > record as u128)
+            .count() as u64
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(winning_range(7, 9), Some((2, 5)));
+        assert_eq!(winning_range(15, 40), Some((4, 11)));
+        assert_eq!(ways_to_win(&PLAIN, 71530, 940200), 71503);
+    }
+
+    #[test]
+    fn roots_on_integers() {
+        // 30^2 - 4*200 = 100: roots are exactly 10 and 20, which only tie
+        assert_eq!(winning_range(30, 200), Some((11, 19)));
+        // 10^2 - 4*24 = 4: roots 4 and 6
+        assert_eq!(winning_range(10, 24), Some((5, 5)));
+        // 10^2 - 4*25 = 0: the best hold time only ties the record
+        assert_eq!(winning_range(10, 25), None);
+        // odd time, 5^2 - 4*6 = 1: roots 2 and 3
+        assert_eq!(winning_range(5, 6), None);
+        assert_eq!(winning_range(5, 5), Some((2, 3)));
+    }
+
+    #[test]
+    fn unreachable_record() {
+        assert_eq!(winning_range(10, 26), None);
+        assert_eq!(winning_range(0, 0), None);
+        assert_eq!(ways_to_win(&PLAIN, 10, 1000), 0);
+    }
+
+    #[test]
+    fn around_perfect_squares() {
+        for time in 0..80 {
+            for record in 0..=time * time / 4 + 1 {
+                assert_eq!(
+                    ways_to_win(&PLAIN, time, record),
+                    brute_force(time, record),
+                    "time {} record {}",
+                    time,
+                    record
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn kerned_numbers() {
+        assert_eq!(kerned_number("Time:      7  15   30").unwrap(), 71530);
+        assert_eq!(
+            kerned_number("Distance: 18446744073709551615").unwrap(),
+            u64::MAX
+        );
+        assert!(kerned_number("Distance: 18446744073709551616").is_err());
+        assert!(kerned_number("Time:").is_err());
+    }
+
+    #[test]
+    fn large_values() {
+        // time^2 overflows u64, roots are exactly 2^30 and 2^33 - 2^30
+        let time = 1 << 33;
+        let record = (1u64 << 30) * (time - (1 << 30));
+        assert_eq!(
+            winning_range(time, record),
+            Some(((1 << 30) + 1, time - (1 << 30) - 1))
+        );
+        let time = 1 << 32;
+        let record = (1u64 << 62) - 1;
+        assert_eq!(winning_range(time, record), Some((1 << 31, 1 << 31)));
+        let record = (1u64 << 62) - (1 << 20);
+        // h * (2^32 - h) > 2^62 - 2^20  <=>  (h - 2^31)^2 < 2^20
+        assert_eq!(
+            winning_range(time, record),
+            Some(((1 << 31) - 1023, (1 << 31) + 1023))
+        );
+    }
+
+    // Winning window found by trying every hold time
+    fn brute_force_range(boat: &Boat, time: u64, record: u64) -> Option<(u64, u64)> {
+        let wins = (0..=time)
+            .filter(|hold| boat.distance(time, *hold) > record as f64)
+            .collect::<Vec<u64>>();
+        Some((*wins.first()?, *wins.last()?))
+    }
+
+    #[test]
+    fn boat_models() {
+        let boats = [
+            Boat {
+                acceleration: 3,
+                max_speed: None,
+                drag: 0.0,
+            },
+            Boat {
+                acceleration: 2,
+                max_speed: Some(7),
+                drag: 0.0,
+            },
+            Boat {
+                acceleration: 3,
+                max_speed: Some(9),
+                drag: 0.0,
+            },
+            Boat {
+                acceleration: 1,
+                max_speed: None,
+                drag: 0.1,
+            },
+            Boat {
+                acceleration: 2,
+                max_speed: Some(10),
+                drag: 0.05,
+            },
+        ];
+        for (b, boat) in boats.iter().enumerate() {
+            for time in 0..40 {
+                for record in 0..200 {
+                    assert_eq!(
+                        boat.winning_range(time, record),
+                        brute_force_range(boat, time, record),
+                        "boat {} time {} record {}",
+                        b,
+                        time,
+                        record
+                    );
+                }
+            }
+        }
+    }
+}
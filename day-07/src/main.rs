@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::par;
 use clap::Parser;
 use itertools::Itertools;
 
@@ -11,6 +12,10 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// number of worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
 }
 
 // Convert Card to it hexadecimal value
@@ -104,27 +109,24 @@ fn build_type_repr(hand: &str, joker: Option<char>) -> String {
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    par::init(args.threads);
 
     let file = File::open(args.input)?;
+    let lines = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .collect::<Vec<String>>();
 
-    let mut games = Vec::<(String, String, u64)>::new();
-    let mut games_joker = Vec::<(String, String, u64)>::new();
-    for line in BufReader::new(file).lines() {
-        let line = line.unwrap();
+    // Score every hand in parallel, with and without joker
+    let scored = par::map(&lines, |line| {
         let (hand, bid) = line.split_at(5);
-        let type_repr = build_type_repr(hand, None);
-        let type_repr_joker = build_type_repr(hand, Some('J'));
-        games.push((
-            hand.to_owned(),
-            type_repr,
-            bid.trim().parse::<u64>().unwrap(),
-        ));
-        games_joker.push((
-            hand.to_owned(),
-            type_repr_joker,
-            bid.trim().parse::<u64>().unwrap(),
-        ));
-    }
+        let bid = bid.trim().parse::<u64>().unwrap();
+        (
+            (hand.to_owned(), build_type_repr(hand, None), bid),
+            (hand.to_owned(), build_type_repr(hand, Some('J')), bid),
+        )
+    });
+    let (mut games, mut games_joker): (Vec<_>, Vec<_>) = scored.into_iter().unzip();
 
     games.sort_by(|(hand1, type1, _), (hand2, type2, _)| {
         if type1 != type2 {
@@ -1,6 +1,7 @@
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::render::{self, Cell};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -9,6 +10,10 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// render the loop to an image file (.png or .svg)
+    #[arg(short, long)]
+    render: Option<String>,
 }
 
 type Pos = (i32, i32);
@@ -21,7 +26,7 @@ fn find_start(map: &[String]) -> (i32, i32) {
         .unwrap()
 }
 
-fn is_valid(map: &Vec<String>, pos: &Pos) -> bool {
+fn is_valid(map: &[String], pos: &Pos) -> bool {
     let (y, x) = pos;
     if *y >= 0 && *y < map.len() as i32 {
         *x >= 0 && *x < map[0].len() as i32
@@ -34,7 +39,7 @@ fn at(map: &[String], pos: &Pos) -> char {
     map[pos.0 as usize].chars().nth(pos.1 as usize).unwrap()
 }
 
-fn add_guess(v: &mut Vec<Pos>, map: &Vec<String>, pos: &Pos, dir: &Pos, pipes: &[char]) -> bool {
+fn add_guess(v: &mut Vec<Pos>, map: &[String], pos: &Pos, dir: &Pos, pipes: &[char]) -> bool {
     let p = (pos.0 + dir.0, pos.1 + dir.1);
     if is_valid(map, &p) {
         let c = at(map, &p);
@@ -122,7 +127,7 @@ fn to_ascii(map: &[String]) -> Vec<String> {
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
 
-    let file = File::open(args.input)?;
+    let file = File::open(&args.input)?;
     let map = BufReader::new(file)
         .lines()
         .map_while(Result::ok)
@@ -139,9 +144,7 @@ fn main() -> std::io::Result<()> {
     let start_char = guess_missing_char(&on_left, &on_right, &on_up, &on_down);
 
     let mut only_loop = (0..map.len())
-        .map(|_x| {
-            " ".repeat(map[0].len())
-        })
+        .map(|_x| " ".repeat(map[0].len()))
         .collect::<Vec<String>>();
 
     let height = map.len();
@@ -181,6 +184,24 @@ fn main() -> std::io::Result<()> {
     });
 
     println!("{}", in_loop);
+
+    if let Some(filename) = &args.render {
+        render::render(filename, width, height, |y, x| {
+            let pos = (y as i32, x as i32);
+            let cell = if at(&only_loop, &pos) != ' ' {
+                Cell::fill((230, 160, 40))
+            } else if is_in_loop(&only_loop, &pos) {
+                Cell::fill((60, 170, 90))
+            } else {
+                Cell::fill((40, 40, 40))
+            };
+            match pos == start {
+                true => cell.with_overlay((220, 40, 40)),
+                false => cell,
+            }
+        })?;
+    }
+
     // 535 too high
     Ok(())
 }
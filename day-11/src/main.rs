@@ -1,6 +1,7 @@
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::{config, par};
 use boolinator::Boolinator;
 use clap::Parser;
 use itertools::Itertools;
@@ -11,6 +12,14 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// number of worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
+
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
 type Pos = (usize, usize);
@@ -86,14 +95,14 @@ fn dilate(sky: &Map2D) -> Map2D {
                     .enumerate()
                     .flat_map(|(i, c)| {
                         let count = empty_cols.contains(&i) as usize + 1;
-                        std::iter::repeat(c).take(count)
+                        std::iter::repeat_n(c, count)
                     })
                     .collect::<String>(),
             )
         })
         .flat_map(|(j, row)| {
             let count = empty_rows.contains(&j) as usize + 1;
-            std::iter::repeat(row).take(count)
+            std::iter::repeat_n(row, count)
         })
         .collect::<Vec<String>>();
 
@@ -140,7 +149,7 @@ fn extract_galaxies(sky: &Map2D) -> Vec<(usize, usize)> {
         .flat_map(|(y, row)| {
             row.chars()
                 .enumerate()
-                .flat_map(move |(x, c)| std::iter::repeat((y, x)).take((c == '#') as usize))
+                .flat_map(move |(x, c)| std::iter::repeat_n((y, x), (c == '#') as usize))
         })
         .collect::<Vec<(usize, usize)>>()
 }
@@ -173,6 +182,10 @@ fn distance(g1: &Pos, g2: &Pos, empty_spaces: &(Vec<usize>, Vec<usize>), space_w
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    par::init(args.threads);
+    let params = config::day_params("day-11", &args.params)?;
+    // rows/columns added for each empty one
+    let expansions: Vec<u64> = params.get("expansions", vec![1, 999999])?;
 
     let sky = Map2D::from_file(&args.input)?;
 
@@ -183,11 +196,10 @@ fn main() -> std::io::Result<()> {
     println!("Galaxies: {:?}", galaxies);
 
     // Iterate all pairs
-    for i in [1, 999999] {
-        let sum: u64 = galaxies
-            .iter()
-            .combinations(2)
-            .map(|v| distance(v[0], v[1], &empty_spaces, i))
+    let pairs = galaxies.iter().combinations(2).collect::<Vec<Vec<&Pos>>>();
+    for i in expansions {
+        let sum: u64 = par::map(&pairs, |v| distance(v[0], v[1], &empty_spaces, i))
+            .into_iter()
             .sum();
         println!("Sum (x{}) is {}", i, sum);
     }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::{config, par};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -10,6 +11,14 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// number of worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
+
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
 type Cache = HashMap<(usize, usize, u32), u64>;
@@ -57,31 +66,36 @@ fn solve_line(
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    par::init(args.threads);
+    let params = config::day_params("day-12", &args.params)?;
+    let unfold: usize = params.get("unfold", 5)?;
 
     let file = File::open(args.input)?;
-    let sum = BufReader::new(file)
+    let rows = BufReader::new(file)
         .lines()
         .map_while(Result::ok)
-        .map(|line| {
-            let (flat, lst) = line.split_once(' ').unwrap();
-            let lst = lst
-                .split(',')
-                .map(str::parse::<u32>)
-                .filter_map(|r| r.ok())
-                .collect::<Vec<u32>>();
-            let res = solve_line(&mut Cache::new(), flat, &lst, 0, 0, 0);
-
-            let flat2 = [flat, "?", flat, "?", flat, "?", flat, "?", flat]
-                .iter()
-                .fold(String::new(), |acc, c| acc + *c);
-            let lst2 = lst.repeat(5);
-            let res2 = solve_line(&mut Cache::new(), &flat2, &lst2, 0, 0, 0);
-
-            (res, res2)
-        })
-        .fold((0_u64, 0_u64), |(sum1, sum2), (res1, res2)| {
-            (sum1 + res1, sum2 + res2)
-        });
+        .collect::<Vec<String>>();
+
+    // Rows are independent, solve them in parallel
+    let sum = par::map(&rows, |line| {
+        let (flat, lst) = line.split_once(' ').unwrap();
+        let lst = lst
+            .split(',')
+            .map(str::parse::<u32>)
+            .filter_map(|r| r.ok())
+            .collect::<Vec<u32>>();
+        let res = solve_line(&mut Cache::new(), flat, &lst, 0, 0, 0);
+
+        let flat2 = vec![flat; unfold].join("?");
+        let lst2 = lst.repeat(unfold);
+        let res2 = solve_line(&mut Cache::new(), &flat2, &lst2, 0, 0, 0);
+
+        (res, res2)
+    })
+    .into_iter()
+    .fold((0_u64, 0_u64), |(sum1, sum2), (res1, res2)| {
+        (sum1 + res1, sum2 + res2)
+    });
 
     println!("Sum1: {}", sum.0);
     println!("Sum2: {}", sum.1);
@@ -1,6 +1,7 @@
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::par;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -9,6 +10,10 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// number of worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
 }
 
 fn diff(l1: &str, l2: &str) -> u32 {
@@ -17,7 +22,7 @@ fn diff(l1: &str, l2: &str) -> u32 {
         .fold(0, |acc, (c1, c2)| acc + (c1 != c2) as u32)
 }
 
-fn is_symmetric(p: &Vec<String>, i: usize, j: usize, d: u32, smudge: u32) -> bool {
+fn is_symmetric(p: &[String], i: usize, j: usize, d: u32, smudge: u32) -> bool {
     // println!("{} / {}  {} / {}",i,j,p[i],p[j]);
     let d = diff(&p[i], &p[j]) + d;
     if d <= smudge {
@@ -31,11 +36,11 @@ fn is_symmetric(p: &Vec<String>, i: usize, j: usize, d: u32, smudge: u32) -> boo
     }
 }
 
-fn find_symmetry(p: &Vec<String>, smudge: u32) -> Option<usize> {
+fn find_symmetry(p: &[String], smudge: u32) -> Option<usize> {
     (1..p.len()).find(|&i| is_symmetric(p, i - 1, i, 0, smudge))
 }
 
-fn swap(p: &Vec<String>) -> Vec<String> {
+fn swap(p: &[String]) -> Vec<String> {
     let width = p.len();
     let height = p[0].len();
 
@@ -53,33 +58,38 @@ fn swap(p: &Vec<String>) -> Vec<String> {
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    par::init(args.threads);
 
-    for smudge in 0..2 {
-        let file = File::open(&args.input)?;
-        let mut lines = BufReader::new(file).lines();
-
-        let mut sum = 0;
-        loop {
-            let p = lines
-                .by_ref()
-                .filter_map(|r| r.ok())
-                .take_while(|line| !line.is_empty())
-                .collect::<Vec<String>>();
-
-            if p.is_empty() {
-                break;
-            }
+    let file = File::open(&args.input)?;
+    let mut lines = BufReader::new(file).lines();
+
+    let mut patterns = Vec::<Vec<String>>::new();
+    loop {
+        let p = lines
+            .by_ref()
+            .filter_map(|r| r.ok())
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<String>>();
 
-            let sym = find_symmetry(&p, smudge);
+        if p.is_empty() {
+            break;
+        }
+        patterns.push(p);
+    }
+
+    for smudge in 0..2 {
+        // Patterns are independent, summarize them in parallel
+        let sum: usize = par::map(&patterns, |p| {
+            let sym = find_symmetry(p, smudge);
             if let Some(pos) = sym {
-                sum += pos * 100;
+                pos * 100
             } else {
-                let swapped = swap(&p);
-                let pos =
-                    find_symmetry(&swapped, smudge).expect("should have one axis of symmetry");
-                sum += pos;
+                let swapped = swap(p);
+                find_symmetry(&swapped, smudge).expect("should have one axis of symmetry")
             }
-        }
+        })
+        .into_iter()
+        .sum();
 
         println!("Sum (smudge {}): {}", smudge, sum);
     }
@@ -1,6 +1,9 @@
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::animate::Animation;
+use aoc_common::config;
+use aoc_common::render::{self, Cell};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -9,6 +12,22 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// render the platform to an image file (.png or .svg)
+    #[arg(short, long)]
+    render: Option<String>,
+
+    /// animate the tilt cycles in the terminal
+    #[arg(short, long)]
+    animate: bool,
+
+    /// delay between two animation frames, in milliseconds
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
 pub struct Map2D {
@@ -108,13 +127,15 @@ impl Map2D {
         self.content.eq(&other.content)
     }
 
+    fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.content
+            .chunks(self.width)
+            .map(|line| line.iter().collect::<String>())
+    }
+
+    #[allow(dead_code)]
     fn print(&self) {
-        (0..self.height).for_each(|y| {
-            let line = self.content[y * self.width..(y + 1) * self.width]
-                .iter()
-                .collect::<String>();
-            println!("{}", line);
-        });
+        self.lines().for_each(|line| println!("{}", line));
     }
 }
 
@@ -165,35 +186,70 @@ fn cycle(map: &mut Map2D) -> Map2D {
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    let params = config::day_params("day-14", &args.params)?;
+    let target: u64 = params.get("cycles", 1000000000)?;
 
     let mut map = Map2D::from_file(&args.input)?;
     let mut map2 = map.clone();
 
+    let mut anim = args
+        .animate
+        .then(|| Animation::start(args.delay))
+        .transpose()?;
+
     stack_north(&mut map2);
-    println!("Score: {}", score(&map2));
+    if let Some(anim) = &mut anim {
+        anim.frame("initial", map.lines())?;
+        anim.frame("tilted north", map2.lines())?;
+    }
+    let part1 = score(&map2);
 
+    // Small targets are simulated directly
+    let warmup = target.min(1000);
     let mut cycles = 0u64;
-    for _i in 0..1000 {
+    for _i in 0..warmup {
         cycles += 1;
         map = cycle(&mut map);
+        if let Some(anim) = &mut anim {
+            anim.frame(&format!("cycle {}", cycles), map.lines())?;
+        }
     }
+    // Give the terminal back before printing the results
+    drop(anim);
+
+    println!("Score: {}", part1);
     println!("cycles {}", cycles);
-    let after1000 = map.clone();
-    loop {
-        cycles += 1;
-        map = cycle(&mut map);
-        if map.equal(&after1000) {
-            break;
+    if cycles < target {
+        let after_warmup = map.clone();
+        loop {
+            cycles += 1;
+            map = cycle(&mut map);
+            if cycles == target || map.equal(&after_warmup) {
+                break;
+            }
         }
     }
-    let rep = cycles - 1000;
-    let rem = (1000000000 - cycles) % rep;
-    for _i in 0..rem {
-        map = cycle(&mut map);
+    if cycles < target {
+        // the map repeats every `rep` cycles
+        let rep = cycles - warmup;
+        let rem = (target - cycles) % rep;
+        for _i in 0..rem {
+            map = cycle(&mut map);
+        }
     }
 
     // map.print();
     println!("Score {}", score(&map));
 
+    if let Some(filename) = &args.render {
+        render::render(filename, map.width, map.height, |y, x| {
+            match map.at((y, x)) {
+                'O' => Cell::fill((40, 40, 40)).with_overlay((200, 200, 210)),
+                '#' => Cell::fill((110, 90, 70)),
+                _ => Cell::fill((40, 40, 40)),
+            }
+        })?;
+    }
+
     Ok(())
 }
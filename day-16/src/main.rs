@@ -1,3 +1,6 @@
+use aoc_common::animate::Animation;
+use aoc_common::par;
+use aoc_common::render::{self, Cell};
 use clap::Parser;
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
@@ -9,6 +12,22 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// number of worker threads (default: all cores)
+    #[arg(short, long)]
+    threads: Option<usize>,
+
+    /// animate the beam propagation of part 1 in the terminal
+    #[arg(short, long)]
+    animate: bool,
+
+    /// delay between two animation frames, in milliseconds
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+
+    /// render the energized tiles of part 1 to an image file (.png or .svg)
+    #[arg(short, long)]
+    render: Option<String>,
 }
 
 pub struct Map2D {
@@ -144,18 +163,20 @@ fn next(pos: &(i32, i32), dir: &(i8, i8)) -> (i32, i32) {
          (0,-1)
 */
 
-fn follow(map: &Map2D, trace: &mut Visited, pos: (i32, i32), dir: (i8, i8)) {
-    let mut to_visit = Vec::<((i32, i32), (i8, i8))>::new();
-    to_visit.push((pos, dir));
+type Beam = ((i32, i32), (i8, i8));
+
+// Advance every beam of the wavefront by one tile, return the next wavefront
+fn propagate(map: &Map2D, trace: &mut Visited, beams: Vec<Beam>) -> Vec<Beam> {
+    let mut next_beams = Vec::<Beam>::new();
 
-    while let Some((pos, dir)) = to_visit.pop() {
+    for (pos, dir) in beams {
         //println!("inspecting {:?}/{:?}", pos, dir);
         let atpos = trace.entry(pos).or_default();
         if !atpos.insert(dir) {
             continue;
         }
 
-        to_visit.extend(
+        next_beams.extend(
             match map.at(&pos) {
                 '/' => vec![(-dir.1, -dir.0)].into_iter(),
                 '\\' => vec![(dir.1, dir.0)].into_iter(),
@@ -172,10 +193,32 @@ fn follow(map: &Map2D, trace: &mut Visited, pos: (i32, i32), dir: (i8, i8)) {
             .filter_map(|dir| {
                 let npos = next(&pos, &dir);
                 map.filter_pos(npos).map(|valid_pos| (valid_pos, dir))
-            })
-            .collect::<Vec<((i32, i32), (i8, i8))>>(),
+            }),
         );
     }
+
+    next_beams
+}
+
+fn follow(map: &Map2D, trace: &mut Visited, pos: (i32, i32), dir: (i8, i8)) {
+    let mut beams = vec![(pos, dir)];
+    while !beams.is_empty() {
+        beams = propagate(map, trace, beams);
+    }
+}
+
+// The map, with energized empty tiles shown as '#'
+fn energized_lines(map: &Map2D, trace: &Visited) -> Vec<String> {
+    (0..map.height)
+        .map(|y| {
+            (0..map.width)
+                .map(|x| match map.at(&(y, x)) {
+                    '.' if trace.contains_key(&(y, x)) => '#',
+                    c => c,
+                })
+                .collect::<String>()
+        })
+        .collect()
 }
 
 fn try_enter(map: &Map2D, pos: (i32, i32), dir: (i8, i8)) -> usize {
@@ -186,37 +229,51 @@ fn try_enter(map: &Map2D, pos: (i32, i32), dir: (i8, i8)) -> usize {
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    par::init(args.threads);
 
     let map = Map2D::from_file(&args.input)?;
 
     // Part 1
     let mut trace = Visited::new();
-    follow(&map, &mut trace, (0, 0), (0, 1));
+    if args.animate {
+        let mut anim = Animation::start(args.delay)?;
+        let mut beams = vec![((0, 0), (0, 1))];
+        while !beams.is_empty() {
+            beams = propagate(&map, &mut trace, beams);
+            let title = format!("{} beams, {} energized", beams.len(), trace.len());
+            anim.frame(&title, energized_lines(&map, &trace))?;
+        }
+    } else {
+        follow(&map, &mut trace, (0, 0), (0, 1));
+    }
     println!("part 1 {}", trace.len());
 
-    let max_vert = (0..map.height)
-        .flat_map(|y| {
-            [
-                try_enter(&map, (y, 0), (0, 1)),
-                try_enter(&map, (y, map.width - 1), (0, -1)),
-            ]
-            .into_iter()
-        })
-        .max()
-        .unwrap();
+    if let Some(filename) = &args.render {
+        render::render(filename, map.width as usize, map.height as usize, |y, x| {
+            let pos = (y as i32, x as i32);
+            let cell = match trace.contains_key(&pos) {
+                true => Cell::fill((250, 200, 50)),
+                false => Cell::fill((30, 30, 30)),
+            };
+            match map.at(&pos) {
+                '.' => cell,
+                _ => cell.with_overlay((120, 120, 140)),
+            }
+        })?;
+    }
 
-    let max_horiz = (0..map.width)
-        .flat_map(|x| {
-            [
-                try_enter(&map, (0, x), (1, 0)),
-                try_enter(&map, (map.height - 1, x), (-1, 0)),
-            ]
-            .into_iter()
-        })
+    // Every edge cell, entering towards the inside of the map
+    let entries = (0..map.height)
+        .flat_map(|y| [((y, 0), (0, 1)), ((y, map.width - 1), (0, -1))])
+        .chain((0..map.width).flat_map(|x| [((0, x), (1, 0)), ((map.height - 1, x), (-1, 0))]))
+        .collect::<Vec<((i32, i32), (i8, i8))>>();
+
+    let best = par::map(&entries, |(pos, dir)| try_enter(&map, *pos, *dir))
+        .into_iter()
         .max()
         .unwrap();
 
-    println!("Part 2 : {}", max_horiz.max(max_vert));
+    println!("Part 2 : {}", best);
 
     Ok(())
 }
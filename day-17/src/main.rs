@@ -3,6 +3,8 @@ use std::collections::{HashMap, VecDeque};
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::config;
+use aoc_common::render::{self, Cell};
 use clap::Parser;
 use strum::IntoEnumIterator; // 0.17.1
 use strum_macros::EnumIter; // 0.17.1
@@ -13,6 +15,14 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// render the best path to an image file (.png or .svg)
+    #[arg(short, long)]
+    render: Option<String>,
+
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
 #[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, EnumIter, Debug)]
@@ -38,7 +48,6 @@ impl Dir {
         (pos.0 as i32 + ofs.0, pos.1 as i32 + ofs.1)
     }
 
-    #[allow(dead_code)]
     pub fn apply_inv(&self, pos: &(usize, usize)) -> (i32, i32) {
         let ofs = self.get_ofs();
         (pos.0 as i32 - ofs.0, pos.1 as i32 - ofs.1)
@@ -54,6 +63,27 @@ impl Dir {
     }
 }
 
+// Number of blocks the crucible moves in a straight line:
+// at least `min` before turning, at most `max`
+struct Runs {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl Runs {
+    // Can the crucible go to `dir`, after `dirlg` blocks towards `from` ?
+    pub fn can_move(&self, from: &Dir, dirlg: usize, dir: &Dir) -> Option<usize> {
+        let next_lg = match dir {
+            d if d == from => dirlg + 1,
+            _ => 1,
+        };
+        let valid = !dir.is_opposite(from)
+            && (next_lg <= self.max)
+            && ((dir == from) || (dirlg == 0) || (dirlg >= self.min));
+        valid.then_some(next_lg)
+    }
+}
+
 #[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Debug)]
 struct Status {
     pub pos: (usize, usize),
@@ -61,10 +91,48 @@ struct Status {
     pub dirlg: usize,
 }
 
+// Walk back from the last status to the start, looking for a predecessor
+// whose cost plus the cost of the current block gives the current cost
+fn backtrack(
+    map: &[Vec<u32>],
+    runs: &Runs,
+    cost_map: &HashMap<Status, u32>,
+    last: &Status,
+) -> Vec<(usize, usize)> {
+    let mut path = vec![last.pos];
+    let mut status = last.clone();
+
+    while status.dirlg > 0 {
+        let dist = cost_map[&status] - map[status.pos.0][status.pos.1];
+        let prev = status.dir.apply_inv(&status.pos);
+        let prev = (prev.0 as usize, prev.1 as usize);
+
+        status = Dir::iter()
+            .flat_map(|dir| (0..=runs.max).map(move |dirlg| (dir.clone(), dirlg)))
+            .filter(|(dir, dirlg)| runs.can_move(dir, *dirlg, &status.dir) == Some(status.dirlg))
+            .map(|(dir, dirlg)| Status {
+                pos: prev,
+                dir,
+                dirlg,
+            })
+            .find(|s| cost_map.get(s) == Some(&dist))
+            .expect("no predecessor found");
+        path.push(status.pos);
+    }
+
+    path
+}
+
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
-
-    let file = File::open(args.input)?;
+    let params = config::day_params("day-17", &args.params)?;
+    // Part 1 is min_run = 1, max_run = 3
+    let runs = Runs {
+        min: params.get("min_run", 4)?,
+        max: params.get("max_run", 10)?,
+    };
+
+    let file = File::open(&args.input)?;
     let reader = BufReader::new(file);
 
     // Parse input
@@ -108,26 +176,16 @@ fn main() -> std::io::Result<()> {
         //        println!("{:?}  {:?}", status, dist);
 
         for dir in Dir::iter() {
-            if dir.is_opposite(&status.dir) {
-                continue;
-            }
-            let newpos = dir.apply(&status.pos);
-            let dirlg = match &dir {
-                d if *d == status.dir => status.dirlg + 1,
-                _ => 1,
+            let dirlg = match runs.can_move(&status.dir, status.dirlg, &dir) {
+                Some(dirlg) => dirlg,
+                None => continue,
             };
+            let newpos = dir.apply(&status.pos);
 
             let pos_valid =
                 (newpos.0 >= 0) && (newpos.0 < height) && (newpos.1 >= 0) && (newpos.1 < width);
 
-            // Part 1:
-            // let long_valid = (dirlg <= 3);
-
-            // Part 2:
-            let long_valid = (dirlg <= 10)
-                && ((dir == status.dir) || (status.dirlg == 0) || (status.dirlg >= 4));
-
-            if pos_valid && long_valid {
+            if pos_valid {
                 let pos = (newpos.0 as usize, newpos.1 as usize);
                 let cost = map[pos.0][pos.1];
                 heap.push_back((Status { pos, dir, dirlg }, dist + cost));
@@ -135,18 +193,27 @@ fn main() -> std::io::Result<()> {
         }
     }
 
-    let min_cost = cost_map
+    let best = cost_map
         .iter()
         .filter_map(|(status, dist)| {
             (status.pos == (height as usize - 1, width as usize - 1)).then_some((status, dist))
         })
-        .map(|x| {
-            println!("{:?}", x);
-            x.1
-        })
-        .min();
-
-    println!("{:?}", min_cost);
+        .inspect(|x| println!("{:?}", x))
+        .min_by_key(|x| x.1);
+
+    println!("{:?}", best.map(|x| x.1));
+
+    if let (Some(filename), Some((last, _))) = (&args.render, best) {
+        let path = backtrack(&map, &runs, &cost_map, last);
+        render::render(filename, width as usize, height as usize, |y, x| {
+            let shade = 255 - 25 * map[y][x] as u8;
+            let cell = Cell::fill((shade, shade, shade));
+            match path.contains(&(y, x)) {
+                true => cell.with_overlay((220, 40, 40)),
+                false => cell,
+            }
+        })?;
+    }
 
     Ok(())
 }
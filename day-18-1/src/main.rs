@@ -2,6 +2,8 @@ use std::collections::VecDeque;
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 
+use aoc_common::config;
+use aoc_common::render::{self, Cell};
 use clap::Parser;
 use itertools::Itertools;
 use strum_macros::EnumIter; // 0.17.1
@@ -12,6 +14,14 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// render the lagoon to an image file (.png or .svg)
+    #[arg(short, long)]
+    render: Option<String>,
+
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
 #[derive(Clone, PartialOrd, Ord, PartialEq, Eq, Hash, EnumIter, Debug)]
@@ -45,6 +55,7 @@ impl From<char> for Dir {
     }
 }
 
+#[allow(dead_code)]
 #[derive(PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Debug)]
 struct Status {
     pub pos: (usize, usize),
@@ -67,7 +78,7 @@ fn print_map_to_file(map: &[Vec<char>], filename: &str) {
     });
 }
 
-fn delimit(map: &mut Vec<Vec<char>>) {
+fn delimit(map: &mut [Vec<char>]) {
     let width = map[0].len() as i32;
     let height = map.len() as i32;
     let mut to_visit = VecDeque::<(i32, i32)>::new();
@@ -103,8 +114,11 @@ fn delimit(map: &mut Vec<Vec<char>>) {
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    let params = config::day_params("day-18-1", &args.params)?;
+    let rebuilt_map: String = params.get("rebuilt_map", "map_rebuilt.txt".to_string())?;
+    let lava_map: String = params.get("lava_map", "map_with_lava.txt".to_string())?;
 
-    let file = File::open(args.input)?;
+    let file = File::open(&args.input)?;
     let mut reader = BufReader::new(file);
 
     // Find map size
@@ -147,13 +161,8 @@ fn main() -> std::io::Result<()> {
 
     // Alloc map
     let mut map = Vec::<Vec<char>>::with_capacity(height as usize);
-    (0..height as usize).for_each(|_| {
-        map.push(
-            std::iter::repeat(' ')
-                .take(width as usize)
-                .collect::<Vec<char>>(),
-        )
-    });
+    (0..height as usize)
+        .for_each(|_| map.push(std::iter::repeat_n(' ', width as usize).collect::<Vec<char>>()));
 
     // Restart a the beginning of the file to draw the path
     reader.seek(std::io::SeekFrom::Start(0))?;
@@ -186,9 +195,22 @@ fn main() -> std::io::Result<()> {
             p
         });
 
-    print_map_to_file(&map, "map_rebuilt.txt");
+    print_map_to_file(&map, &rebuilt_map);
     delimit(&mut map);
-    print_map_to_file(&map, "map_with_lava.txt");
+    print_map_to_file(&map, &lava_map);
+
+    if let Some(filename) = &args.render {
+        render::render(
+            filename,
+            width as usize,
+            height as usize,
+            |y, x| match map[y][x] {
+                '#' => Cell::fill((120, 70, 30)),
+                '-' => Cell::fill((40, 40, 40)),
+                _ => Cell::fill((230, 90, 20)),
+            },
+        )?;
+    }
 
     let count = map.iter().fold(0, |acc, v| {
         acc + v.iter().fold(0_usize, |acc, c| acc + (*c != '-') as usize)
@@ -1,3 +1,4 @@
+use aoc_common::animate::Animation;
 use clap::Parser;
 use num::Integer;
 use std::cell::RefCell;
@@ -12,6 +13,14 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// animate the module states of the first 1000 button pushes
+    #[arg(short, long)]
+    animate: bool,
+
+    /// delay between two animation frames, in milliseconds
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
 }
 
 /*
@@ -79,10 +88,34 @@ impl Element {
 
 type EltMap = HashMap<String, Rc<RefCell<Element>>>;
 
+// Modules sorted by name, a few per line: '#' when high, '.' when low
+fn state_lines(elts: &EltMap) -> Vec<String> {
+    let mut names = elts.keys().collect::<Vec<&String>>();
+    names.sort();
+
+    names
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|name| {
+                    let elt = elts[*name].as_ref().borrow();
+                    let kind = match elt.elt_type {
+                        EltType::FlipFlop => '%',
+                        EltType::Conjuction(_) => '&',
+                    };
+                    let state = if elt.state { '#' } else { '.' };
+                    format!("{}{:<4}{}  ", kind, name, state)
+                })
+                .collect::<String>()
+        })
+        .collect()
+}
+
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
 
-    let file = File::open(args.input)?;
+    let file = File::open(&args.input)?;
 
     let re = regex::Regex::new("([&%]*)(.+) -> (.+)").unwrap();
     let mut elts = EltMap::new();
@@ -125,7 +158,7 @@ fn main() -> std::io::Result<()> {
     elts.iter().for_each(|(id, elt)| {
         elt.as_ref().borrow().targets.iter().for_each(|t| {
             if let Some(e) = elts.get(t) {
-                e.borrow_mut().add_ref((&id, &elt));
+                e.borrow_mut().add_ref((id, elt));
             } else if t == "rx" {
                 // assume the last stage is a Conjonction, just check it is
                 assert!(matches!(
@@ -154,6 +187,13 @@ fn main() -> std::io::Result<()> {
     let mut count_low: u64 = 0;
     let mut count_high: u64 = 0;
     let mut cycle_detected = false;
+    // Results and warnings wait for the end of the animation to be printed
+    let mut messages = Vec::<String>::new();
+
+    let mut anim = args
+        .animate
+        .then(|| Animation::start(args.delay))
+        .transpose()?;
 
     for i in 1..10000 {
         // push the button
@@ -179,7 +219,10 @@ fn main() -> std::io::Result<()> {
                                         if (i % *val) == 0 {
                                             (*cnt + 1, *val)
                                         } else {
-                                            println!(" /!\\ reset cycle {:?} {:?} {}", id, c, i);
+                                            messages.push(format!(
+                                                " /!\\ reset cycle {:?} {:?} {}",
+                                                id, c, i
+                                            ));
                                             (1, i)
                                         }
                                     }
@@ -195,18 +238,30 @@ fn main() -> std::io::Result<()> {
             }
         }
 
+        if let Some(anim) = &mut anim {
+            let title = format!("push {}, {} low / {} high", i, count_low, count_high);
+            anim.frame(&title, state_lines(&elts))?;
+        }
+
         if i == 1000 {
-            println!("Part 1 : {}", count_low * count_high);
+            // Give the terminal back before printing the results
+            anim = None;
+            messages.push(format!("Part 1 : {}", count_low * count_high));
         }
         if cycle_detected {
             let lcm = cycles
                 .values()
                 .map(|(_cnt, val)| val)
                 .fold(1, |acc, c| acc.lcm(c));
-            println!("Part 2 : {}", lcm);
-            if i > 1000 {
-                break;
-            }
+            messages.push(format!("Part 2 : {}", lcm));
+        }
+        if anim.is_none() {
+            messages
+                .drain(..)
+                .for_each(|message| println!("{}", message));
+        }
+        if cycle_detected && i > 1000 {
+            break;
         }
     }
 
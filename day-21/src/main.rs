@@ -1,3 +1,6 @@
+use aoc_common::animate::Animation;
+use aoc_common::config;
+use aoc_common::render::{self, Cell};
 use clap::Parser;
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
@@ -8,6 +11,22 @@ struct Args {
     /// file to parse
     #[arg(short, long)]
     input: String,
+
+    /// render the garden to an image file (.png or .svg)
+    #[arg(short, long)]
+    render: Option<String>,
+
+    /// animate the step expansion in the terminal
+    #[arg(short, long)]
+    animate: bool,
+
+    /// delay between two animation frames, in milliseconds
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+
+    /// override a parameter of aoc.toml, as key=value
+    #[arg(short = 'D', long = "param", value_name = "KEY=VALUE")]
+    params: Vec<String>,
 }
 
 type Vec2D = Vec<Vec<char>>;
@@ -60,10 +79,8 @@ impl Map2D {
         }
     }
 
-    pub fn print(&self) {
-        self.map.iter().for_each(|line| {
-            println!("{}", line.iter().collect::<String>());
-        })
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.map.iter().map(|line| line.iter().collect::<String>())
     }
 }
 
@@ -94,8 +111,10 @@ fn count(map: &Map2D) -> usize {
 
 fn main() -> std::io::Result<()> {
     let args = Args::parse();
+    let params = config::day_params("day-21", &args.params)?;
+    let steps: usize = params.get("steps", 64)?;
 
-    let file = File::open(args.input)?;
+    let file = File::open(&args.input)?;
 
     let mut map = Map2D::from(
         BufReader::new(&file)
@@ -119,12 +138,32 @@ fn main() -> std::io::Result<()> {
 
     map.set(&start, 'O');
 
-    for _ in 0..64 {
+    let mut anim = args
+        .animate
+        .then(|| Animation::start(args.delay))
+        .transpose()?;
+
+    for i in 0..steps {
         map = step(&map);
-        println!();
-        map.print();
+        if let Some(anim) = &mut anim {
+            anim.frame(&format!("step {}", i + 1), map.lines())?;
+        }
     }
+    drop(anim);
 
     println!("Count = {}", count(&map));
+
+    if let Some(filename) = &args.render {
+        render::render(
+            filename,
+            map.width as usize,
+            map.height as usize,
+            |y, x| match map.map[y][x] {
+                'O' => Cell::fill((60, 150, 60)).with_overlay((240, 220, 80)),
+                '#' => Cell::fill((90, 90, 90)),
+                _ => Cell::fill((60, 150, 60)),
+            },
+        )?;
+    }
     Ok(())
 }